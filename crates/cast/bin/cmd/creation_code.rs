@@ -0,0 +1,113 @@
+use alloy_primitives::Address;
+use clap::Parser;
+use ethers_core::types::{Action, NameOrAddress, Res};
+use ethers_providers::Middleware;
+use eyre::{eyre, Context, Result};
+use foundry_block_explorers::Client;
+use foundry_cli::{
+    opts::{EtherscanOpts, RpcOpts},
+    utils,
+};
+use foundry_common::{
+    etherscan::contract_creation_tx,
+    provider::ethers::RetryProvider,
+    types::{ToAlloy, ToEthers},
+};
+use foundry_config::{
+    figment::{self, value::Dict, Metadata, Profile},
+    impl_figment_convert_cast, Config,
+};
+use foundry_evm::constants::DEFAULT_CREATE2_DEPLOYER;
+use std::str::FromStr;
+
+/// CLI arguments for `cast creation-code`.
+#[derive(Clone, Debug, Parser)]
+pub struct CreationCodeArgs {
+    /// The contract address.
+    #[clap(value_parser = NameOrAddress::from_str)]
+    address: NameOrAddress,
+
+    #[clap(flatten)]
+    rpc: RpcOpts,
+
+    #[clap(flatten)]
+    etherscan: EtherscanOpts,
+}
+
+impl_figment_convert_cast!(CreationCodeArgs);
+
+impl figment::Provider for CreationCodeArgs {
+    fn metadata(&self) -> Metadata {
+        Metadata::named("CreationCodeArgs")
+    }
+
+    fn data(&self) -> Result<figment::value::Map<Profile, Dict>, figment::Error> {
+        let mut dict = self.rpc.dict();
+        dict.extend(self.etherscan.dict());
+        Ok(figment::value::Map::from([(Config::selected_profile(), dict)]))
+    }
+}
+
+impl CreationCodeArgs {
+    pub async fn run(self) -> Result<()> {
+        let config = Config::from(&self);
+        let provider = utils::get_provider(&config)?;
+
+        let address = match self.address {
+            NameOrAddress::Address(address) => address.to_alloy(),
+            NameOrAddress::Name(name) => provider.resolve_name(&name).await?.to_alloy(),
+        };
+
+        let chain = utils::get_chain(config.chain, &provider).await?;
+        let api_key = config.get_etherscan_api_key(Some(chain)).unwrap_or_default();
+        let client = Client::new(chain, api_key.clone())?;
+
+        let code = creation_code(&provider, &client, &api_key, address).await?;
+        println!("{}", hex::encode_prefixed(code));
+        Ok(())
+    }
+}
+
+/// Returns the creation code of the contract at `address`, i.e. the init code including the
+/// constructor arguments.
+///
+/// The creation transaction is looked up on etherscan. Contracts deployed by factories are found
+/// by tracing the creation transaction, which requires an RPC endpoint that supports
+/// `trace_transaction`.
+pub async fn creation_code(
+    provider: &RetryProvider,
+    client: &Client,
+    api_key: &str,
+    address: Address,
+) -> Result<Vec<u8>> {
+    let tx_hash = contract_creation_tx(client, api_key, address).await?;
+    let tx = provider
+        .get_transaction(tx_hash.to_ethers())
+        .await?
+        .ok_or_else(|| eyre!("Creation transaction {tx_hash} not found"))?;
+
+    match tx.to {
+        None => Ok(tx.input.to_vec()),
+        // CREATE2 deployments through the default deployer are prefixed with the 32 byte salt
+        Some(to) if to.to_alloy() == DEFAULT_CREATE2_DEPLOYER => {
+            Ok(tx.input.get(32..).unwrap_or_default().to_vec())
+        }
+        // created by a factory, find the `CREATE` or `CREATE2` that deployed the contract
+        Some(_) => {
+            let traces = provider.trace_transaction(tx_hash.to_ethers()).await.wrap_err(
+                "Failed to trace the creation transaction, the RPC endpoint must support `trace_transaction` for contracts created by other contracts",
+            )?;
+            traces
+                .into_iter()
+                .find_map(|trace| match (trace.action, trace.result) {
+                    (Action::Create(create), Some(Res::Create(created)))
+                        if created.address.to_alloy() == address =>
+                    {
+                        Some(create.init.to_vec())
+                    }
+                    _ => None,
+                })
+                .ok_or_else(|| eyre!("Contract {address} was not created by transaction {tx_hash}"))
+        }
+    }
+}
@@ -49,18 +49,17 @@ impl InterfaceArgs {
             name,
             pragma,
             output: output_location,
-            etherscan,
+            mut etherscan,
             json,
         } = self;
         let source = if Path::new(&path_or_address).exists() {
             AbiPath::Local { path: path_or_address, name }
         } else {
             let config = Config::from(&etherscan);
-            let chain = config.chain.unwrap_or_default();
-            let api_key = config.get_etherscan_api_key(Some(chain)).unwrap_or_default();
+            let chain = etherscan.resolve(&config)?;
             AbiPath::Etherscan {
                 chain,
-                api_key,
+                api_key: etherscan.key.unwrap_or_default(),
                 address: path_or_address.parse().wrap_err("invalid path or address")?,
             }
         };
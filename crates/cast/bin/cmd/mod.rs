@@ -9,6 +9,7 @@ pub mod access_list;
 pub mod bind;
 pub mod call;
 pub mod create2;
+pub mod creation_code;
 pub mod estimate;
 pub mod find_block;
 pub mod interface;
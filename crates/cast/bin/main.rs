@@ -10,7 +10,7 @@ use ethers_providers::Middleware;
 use eyre::Result;
 use foundry_cli::{handler, prompt, stdin, utils};
 use foundry_common::{
-    abi::get_event,
+    abi::get_func,
     fmt::format_tokens,
     fs,
     selectors::{
@@ -19,7 +19,7 @@ use foundry_common::{
     },
     types::{ToAlloy, ToEthers},
 };
-use foundry_config::Config;
+use foundry_config::{Chain, Config};
 use std::time::Instant;
 
 pub mod cmd;
@@ -153,13 +153,23 @@ async fn main() -> Result<()> {
         }
 
         // ABI encoding & decoding
-        Subcommands::AbiDecode { sig, calldata, input } => {
-            let tokens = SimpleCast::abi_decode(&sig, &calldata, input)?;
+        Subcommands::AbiDecode { sig, calldata, input, .. } => {
+            // the selector is optional when decoding input data
+            let selector = get_func(&sig)?.selector();
+            let tokens = if input && hex::decode(&calldata)?.starts_with(selector.as_slice()) {
+                SimpleCast::calldata_decode(&sig, &calldata, true)?
+            } else {
+                SimpleCast::abi_decode(&sig, &calldata, input)?
+            };
             let tokens = format_tokens(&tokens);
             tokens.for_each(|t| println!("{t}"));
         }
-        Subcommands::AbiEncode { sig, args } => {
-            println!("{}", SimpleCast::abi_encode(&sig, &args)?);
+        Subcommands::AbiEncode { sig, args, packed } => {
+            if packed {
+                println!("{}", SimpleCast::abi_encode_packed(&sig, &args)?);
+            } else {
+                println!("{}", SimpleCast::abi_encode(&sig, &args)?);
+            }
         }
         Subcommands::CalldataDecode { sig, calldata } => {
             let tokens = SimpleCast::calldata_decode(&sig, &calldata, true)?;
@@ -236,7 +246,11 @@ async fn main() -> Result<()> {
         Subcommands::Chain { rpc } => {
             let config = Config::from(&rpc);
             let provider = utils::get_provider(&config)?;
-            println!("{}", Cast::new(provider).chain().await?);
+            match Cast::new(provider.clone()).chain().await? {
+                Some(name) => println!("{name}"),
+                // chains without a well known genesis block are named by their chain ID
+                None => println!("{}", Chain::from_id(utils::get_chain_id(&provider).await?)),
+            }
         }
         Subcommands::ChainId { rpc } => {
             let config = Config::from(&rpc);
@@ -253,6 +267,7 @@ async fn main() -> Result<()> {
             let provider = utils::get_provider(&config)?;
             println!("{}", Cast::new(provider).code(who, block, disassemble).await?);
         }
+        Subcommands::CreationCode(cmd) => cmd.run().await?,
         Subcommands::Codesize { block, who, rpc } => {
             let config = Config::from(&rpc);
             let provider = utils::get_provider(&config)?;
@@ -456,8 +471,7 @@ async fn main() -> Result<()> {
         }
         Subcommands::SigEvent { event_string } => {
             let event_string = stdin::unwrap_line(event_string)?;
-            let parsed_event = get_event(&event_string)?;
-            println!("{:?}", parsed_event.selector());
+            println!("{}", SimpleCast::get_event_selector(&event_string)?);
         }
         Subcommands::LeftShift { value, bits, base_in, base_out } => {
             println!("{}", SimpleCast::left_shift(&value, &bits, base_in.as_deref(), &base_out)?);
@@ -465,10 +479,10 @@ async fn main() -> Result<()> {
         Subcommands::RightShift { value, bits, base_in, base_out } => {
             println!("{}", SimpleCast::right_shift(&value, &bits, base_in.as_deref(), &base_out)?);
         }
-        Subcommands::EtherscanSource { address, directory, etherscan } => {
+        Subcommands::EtherscanSource { address, directory, mut etherscan } => {
             let config = Config::from(&etherscan);
-            let chain = config.chain.unwrap_or_default();
-            let api_key = config.get_etherscan_api_key(Some(chain)).unwrap_or_default();
+            let chain = etherscan.resolve(&config)?;
+            let api_key = etherscan.key.unwrap_or_default();
             match directory {
                 Some(dir) => {
                     SimpleCast::expand_etherscan_source_to_directory(chain, address, api_key, dir)
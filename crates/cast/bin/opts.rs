@@ -1,7 +1,8 @@
 use crate::cmd::{
     access_list::AccessListArgs, bind::BindArgs, call::CallArgs, create2::Create2Args,
-    estimate::EstimateArgs, find_block::FindBlockArgs, interface::InterfaceArgs, logs::LogsArgs,
-    rpc::RpcArgs, run::RunArgs, send::SendTxArgs, storage::StorageArgs, wallet::WalletSubcommands,
+    creation_code::CreationCodeArgs, estimate::EstimateArgs, find_block::FindBlockArgs,
+    interface::InterfaceArgs, logs::LogsArgs, rpc::RpcArgs, run::RunArgs, send::SendTxArgs,
+    storage::StorageArgs, wallet::WalletSubcommands,
 };
 use alloy_primitives::{Address, B256, U256};
 use clap::{Parser, Subcommand, ValueHint};
@@ -471,7 +472,8 @@ pub enum Subcommands {
     ///
     /// Defaults to decoding output data. To decode input data pass --input.
     ///
-    /// When passing `--input`, function selector must NOT be prefixed in `calldata` string
+    /// When passing `--input`, the function selector is stripped from the `calldata` string, if
+    /// present.
     #[clap(name = "abi-decode", visible_aliases = &["ad", "--abi-decode"])]
     AbiDecode {
         /// The function signature in the format `<name>(<in-types>)(<out-types>)`.
@@ -483,6 +485,10 @@ pub enum Subcommands {
         /// Whether to decode the input or output data.
         #[clap(long, short, help_heading = "Decode input data instead of output data")]
         input: bool,
+
+        /// Decode the output data. This is the default.
+        #[clap(long, short, conflicts_with = "input")]
+        output: bool,
     },
 
     /// ABI encode the given function argument, excluding the selector.
@@ -494,6 +500,10 @@ pub enum Subcommands {
         /// The arguments of the function.
         #[clap(allow_hyphen_values = true)]
         args: Vec<String>,
+
+        /// Whether to use packed encoding, as done by `abi.encodePacked`.
+        #[clap(long)]
+        packed: bool,
     },
 
     /// Compute the storage slot for an entry in a mapping.
@@ -660,6 +670,12 @@ pub enum Subcommands {
         rpc: RpcOpts,
     },
 
+    /// Get the creation code of a contract, including the constructor arguments.
+    ///
+    /// The creation transaction is looked up on Etherscan.
+    #[clap(visible_alias = "cc")]
+    CreationCode(CreationCodeArgs),
+
     /// Get the runtime bytecode size of a contract.
     #[clap(visible_alias = "cs")]
     Codesize {
@@ -769,12 +785,15 @@ pub enum Subcommands {
     },
 
     /// Get the source code of a contract from Etherscan.
-    #[clap(visible_aliases = &["et", "src"])]
+    ///
+    /// Contracts verified as standard json input are printed as concatenated files, unless
+    /// `-d` is set.
+    #[clap(visible_aliases = &["source", "et", "src"])]
     EtherscanSource {
         /// The contract's address.
         address: String,
 
-        /// The output directory to expand source tree into.
+        /// The output directory to expand the source tree into, one file per source file.
         #[clap(short, value_hint = ValueHint::DirPath)]
         directory: Option<PathBuf>,
 
@@ -937,6 +956,52 @@ mod tests {
         };
     }
 
+    #[test]
+    fn parse_abi_decode() {
+        let args: Opts = Opts::parse_from(["foundry-cli", "abi-decode", "f(uint256)", "0x", "-i"]);
+        match args.sub {
+            Subcommands::AbiDecode { input, output, .. } => {
+                assert!(input);
+                assert!(!output);
+            }
+            _ => unreachable!(),
+        };
+
+        let res =
+            Opts::try_parse_from(["foundry-cli", "abi-decode", "f(uint256)", "0x", "-i", "-o"]);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn parse_source() {
+        let args: Opts = Opts::parse_from([
+            "foundry-cli",
+            "source",
+            "0xBB9bc244D798123fDe783fCc1C72d3Bb8C189413",
+            "-d",
+            "out",
+        ]);
+        match args.sub {
+            Subcommands::EtherscanSource { address, directory, .. } => {
+                assert_eq!(address, "0xBB9bc244D798123fDe783fCc1C72d3Bb8C189413");
+                assert_eq!(directory, Some(PathBuf::from("out")));
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn parse_creation_code() {
+        let args: Opts = Opts::parse_from([
+            "foundry-cli",
+            "cc",
+            "0xBB9bc244D798123fDe783fCc1C72d3Bb8C189413",
+            "--chain",
+            "mainnet",
+        ]);
+        assert!(matches!(args.sub, Subcommands::CreationCode(_)));
+    }
+
     // <https://github.com/foundry-rs/book/issues/1019>
     #[test]
     fn parse_signature() {
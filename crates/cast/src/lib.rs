@@ -16,7 +16,7 @@ use evm_disassembler::{disassemble_bytes, disassemble_str, format_operations};
 use eyre::{Context, ContextCompat, Result};
 use foundry_block_explorers::Client;
 use foundry_common::{
-    abi::{encode_function_args, get_func},
+    abi::{encode_function_args, encode_function_args_packed, get_event, get_func},
     fmt::*,
     types::{ToAlloy, ToEthers},
     TransactionReceiptWithRevertReason,
@@ -400,7 +400,10 @@ where
         Cast::block_field_as_num(self, block, "timestamp".to_string()).await
     }
 
-    pub async fn chain(&self) -> Result<&str> {
+    /// Returns the name of the chain, identified by its genesis block hash.
+    ///
+    /// Returns `None` if the genesis block isn't one of a well known chain.
+    pub async fn chain(&self) -> Result<Option<&str>> {
         let genesis_hash = Cast::block(
             self,
             0,
@@ -411,7 +414,7 @@ where
         )
         .await?;
 
-        Ok(match &genesis_hash[..] {
+        Ok(Some(match &genesis_hash[..] {
             "0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3" => {
                 match &(Cast::block(self, 1920000, false, Some("hash".to_string()), false).await?)[..]
                 {
@@ -455,12 +458,13 @@ where
                     _ => "avalanche",
                 }
             }
-            _ => "unknown",
-        })
+            _ => return Ok(None),
+        }))
     }
 
+    /// Returns the chain ID of the provider, see [`foundry_cli::utils::get_chain_id`].
     pub async fn chain_id(&self) -> Result<U256> {
-        Ok(self.provider.get_chainid().await?.to_alloy())
+        Ok(U256::from(foundry_cli::utils::get_chain_id(&self.provider).await?))
     }
 
     pub async fn block_number(&self) -> Result<U64> {
@@ -1483,7 +1487,8 @@ impl SimpleCast {
 
     /// Decodes calldata-encoded hex input or output
     ///
-    /// Similar to `abi_decode`, but `calldata` string MUST be prefixed with function selector
+    /// Similar to `abi_decode`, but the input `calldata` string MUST be prefixed with the function
+    /// selector. Use `abi_decode` for data without selector, like constructor arguments.
     ///
     /// # Example
     ///
@@ -1549,6 +1554,34 @@ impl SimpleCast {
         Ok(format!("0x{encoded}"))
     }
 
+    /// Performs packed ABI encoding based off of the function signature, as done by
+    /// `abi.encodePacked`. Does not include the function selector in the result.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cast::SimpleCast as Cast;
+    ///
+    /// assert_eq!(
+    ///     "0x010000000000000000000000000000000000000002",
+    ///     Cast::abi_encode_packed(
+    ///         "f(uint8 a, address b)",
+    ///         &["1", "0x0000000000000000000000000000000000000002"]
+    ///     )
+    ///     .unwrap()
+    ///     .as_str()
+    /// );
+    /// # Ok::<_, eyre::Report>(())
+    /// ```
+    pub fn abi_encode_packed(sig: &str, args: &[impl AsRef<str>]) -> Result<String> {
+        let func = get_func(sig)?;
+        let encoded = match encode_function_args_packed(&func, args) {
+            Ok(res) => res,
+            Err(e) => eyre::bail!("Could not ABI encode the function and arguments. Did you pass in the right types?\nError\n{}", e),
+        };
+        Ok(hex::encode_prefixed(encoded))
+    }
+
     /// Performs ABI encoding to produce the hexadecimal calldata with the given arguments.
     ///
     /// # Example
@@ -1916,6 +1949,23 @@ impl SimpleCast {
         }
     }
 
+    /// Gets the 32-byte topic hash for a given event signature
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cast::SimpleCast as Cast;
+    ///
+    /// assert_eq!(
+    ///     Cast::get_event_selector("Transfer(address indexed from, address indexed to, uint256)")?,
+    ///     "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+    /// );
+    /// # Ok::<(), eyre::Error>(())
+    /// ```
+    pub fn get_event_selector(signature: &str) -> Result<String> {
+        Ok(get_event(signature)?.selector().to_string())
+    }
+
     /// Extracts function selectors and arguments from bytecode
     ///
     /// # Example
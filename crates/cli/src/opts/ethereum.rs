@@ -88,6 +88,9 @@ impl RpcOpts {
 #[derive(Clone, Debug, Default, Serialize, Parser)]
 pub struct EtherscanOpts {
     /// The Etherscan (or equivalent) API key.
+    ///
+    /// Contract verification accepts a comma separated list of keys, the next key is used when
+    /// one hits the rate limit.
     #[clap(short = 'e', long = "etherscan-api-key", alias = "api-key", env = "ETHERSCAN_API_KEY")]
     #[serde(rename = "etherscan_api_key", skip_serializing_if = "Option::is_none")]
     pub key: Option<String>,
@@ -125,11 +128,70 @@ impl EtherscanOpts {
         }
     }
 
+    /// Resolves the chain and the matching etherscan API key from the [Config] and sets them on
+    /// these options.
+    ///
+    /// A key passed with `--etherscan-api-key` is kept. Otherwise a chain specific API key env
+    /// var, e.g. `POLYGONSCAN_API_KEY`, takes precedence over the `ETHERSCAN_API_KEY` env var and
+    /// the key resolved from the config.
+    ///
+    /// Returns the resolved chain.
+    pub fn resolve(&mut self, config: &Config) -> Result<Chain> {
+        self.resolve_with_env(config, false, |var| std::env::var(var).ok())
+    }
+
+    /// Same as [Self::resolve], but for a multichain API that accepts a single key for all
+    /// chains, like the etherscan v2 API.
+    ///
+    /// A key passed with `--etherscan-api-key`, set in the `ETHERSCAN_API_KEY` env var or as
+    /// `etherscan_api_key` in the config is kept, the chain specific keys are only used if none is
+    /// set.
+    pub fn resolve_multichain(&mut self, config: &Config) -> Result<Chain> {
+        self.resolve_with_env(config, true, |var| std::env::var(var).ok())
+    }
+
+    /// Same as [Self::resolve], but looks up env vars with the given `env` function.
+    fn resolve_with_env(
+        &mut self,
+        config: &Config,
+        multichain: bool,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<Chain> {
+        let chain = config.chain.unwrap_or_default();
+        self.chain = Some(chain);
+        let generic_key = env("ETHERSCAN_API_KEY");
+        if multichain {
+            let key = self.key.clone().or_else(|| generic_key.clone());
+            if let Some(key) = key.or_else(|| config.etherscan_api_key.clone()) {
+                self.key = Some(key);
+                return Ok(chain)
+            }
+        }
+        // clap also fills in the key from `ETHERSCAN_API_KEY`, only an explicit key is kept
+        let explicit = self.key.is_some() && self.key != generic_key;
+        self.key = match chain_etherscan_api_key(chain, &env).filter(|_| !explicit) {
+            Some(key) => Some(key),
+            None => config.get_etherscan_config_with_chain(Some(chain))?.map(|c| c.key),
+        };
+        Ok(chain)
+    }
+
     pub fn dict(&self) -> Dict {
         Value::serialize(self).unwrap().into_dict().unwrap()
     }
 }
 
+/// Returns the API key set in the explorer specific env var of the given chain, if any.
+///
+/// The generic `ETHERSCAN_API_KEY` is ignored here, since it's already part of the [Config].
+fn chain_etherscan_api_key(chain: Chain, env: impl Fn(&str) -> Option<String>) -> Option<String> {
+    let var = chain.named()?.etherscan_api_key_name()?;
+    if var == "ETHERSCAN_API_KEY" {
+        return None
+    }
+    env(var).filter(|key| !key.trim().is_empty())
+}
+
 #[derive(Clone, Debug, Default, Parser)]
 #[clap(next_help_heading = "Ethereum options")]
 pub struct EthereumOpts {
@@ -168,3 +230,56 @@ impl figment::Provider for EthereumOpts {
         Ok(Map::from([(Config::selected_profile(), dict)]))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use foundry_config::NamedChain;
+
+    #[test]
+    fn can_resolve_chain_specific_api_key() {
+        let chain = Chain::from_named(NamedChain::Polygon);
+        let config = Config { chain: Some(chain), ..Default::default() };
+        let env = |var: &str| match var {
+            "POLYGONSCAN_API_KEY" => Some("polygonscan-key".to_string()),
+            "ETHERSCAN_API_KEY" => Some("etherscan-key".to_string()),
+            _ => None,
+        };
+
+        // the chain specific key replaces the generic one
+        let mut etherscan = EtherscanOpts { key: env("ETHERSCAN_API_KEY"), ..Default::default() };
+        etherscan.resolve_with_env(&config, false, env).unwrap();
+        assert_eq!(etherscan.chain, Some(chain));
+        assert_eq!(etherscan.key.as_deref(), Some("polygonscan-key"));
+
+        // an explicit `--etherscan-api-key` wins
+        let mut etherscan =
+            EtherscanOpts { key: Some("explicit-key".to_string()), ..Default::default() };
+        let config = Config { etherscan_api_key: etherscan.key.clone(), ..config };
+        etherscan.resolve_with_env(&config, false, env).unwrap();
+        assert_eq!(etherscan.key.as_deref(), Some("explicit-key"));
+    }
+
+    #[test]
+    fn can_resolve_multichain_api_key() {
+        let chain = Chain::from_named(NamedChain::Polygon);
+        let config = Config { chain: Some(chain), ..Default::default() };
+        let env = |var: &str| match var {
+            "POLYGONSCAN_API_KEY" => Some("polygonscan-key".to_string()),
+            "ETHERSCAN_API_KEY" => Some("etherscan-key".to_string()),
+            _ => None,
+        };
+
+        // the generic key is kept
+        let mut etherscan = EtherscanOpts { key: env("ETHERSCAN_API_KEY"), ..Default::default() };
+        etherscan.resolve_with_env(&config, true, env).unwrap();
+        assert_eq!(etherscan.chain, Some(chain));
+        assert_eq!(etherscan.key.as_deref(), Some("etherscan-key"));
+
+        // the chain specific key is used if there's no other
+        let env = |var: &str| env(var).filter(|_| var != "ETHERSCAN_API_KEY");
+        let mut etherscan = EtherscanOpts::default();
+        etherscan.resolve_with_env(&config, true, env).unwrap();
+        assert_eq!(etherscan.key.as_deref(), Some("polygonscan-key"));
+    }
+}
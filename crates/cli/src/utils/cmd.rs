@@ -73,26 +73,31 @@ pub fn get_cached_entry_by_name(
     cache: &SolFilesCache,
     name: &str,
 ) -> Result<(PathBuf, CacheEntry)> {
-    let mut cached_entry = None;
+    let mut cached_entries = Vec::new();
     let mut alternatives = Vec::new();
 
     for (abs_path, entry) in cache.files.iter() {
         for (artifact_name, _) in entry.artifacts.iter() {
             if artifact_name == name {
-                if cached_entry.is_some() {
-                    eyre::bail!(
-                        "contract with duplicate name `{}`. please pass the path instead",
-                        name
-                    )
-                }
-                cached_entry = Some((abs_path.to_owned(), entry.to_owned()));
+                cached_entries.push((abs_path.to_owned(), entry.to_owned()));
             } else {
                 alternatives.push(artifact_name);
             }
         }
     }
 
-    if let Some(entry) = cached_entry {
+    if cached_entries.len() > 1 {
+        let candidates = cached_entries
+            .iter()
+            .map(|(path, _)| format!("  {}:{name}", path.display()))
+            .collect::<Vec<_>>()
+            .join("\n");
+        eyre::bail!(
+            "contract with duplicate name `{name}`. please pass the path instead, one of:\n{candidates}"
+        )
+    }
+
+    if let Some(entry) = cached_entries.pop() {
         return Ok(entry);
     }
 
@@ -300,6 +305,9 @@ where
 }
 
 /// Read contract constructor arguments from the given file.
+///
+/// The file is either a json array or a list of whitespace separated arguments, in which case
+/// lines starting with `#` are treated as comments and ignored.
 pub fn read_constructor_args_file(constructor_args_path: PathBuf) -> Result<Vec<String>> {
     if !constructor_args_path.exists() {
         eyre::bail!("Constructor args file \"{}\" not found", constructor_args_path.display());
@@ -310,7 +318,12 @@ pub fn read_constructor_args_file(constructor_args_path: PathBuf) -> Result<Vec<
             constructor_args_path.display(),
         ))?
     } else {
-        fs::read_to_string(constructor_args_path)?.split_whitespace().map(str::to_string).collect()
+        fs::read_to_string(constructor_args_path)?
+            .lines()
+            .filter(|line| !line.trim_start().starts_with('#'))
+            .flat_map(str::split_whitespace)
+            .map(str::to_string)
+            .collect()
     };
     Ok(args)
 }
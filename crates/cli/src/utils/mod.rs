@@ -4,7 +4,10 @@ use ethers_core::types::TransactionReceipt;
 use ethers_providers::Middleware;
 use eyre::{ContextCompat, Result};
 use foundry_common::types::ToAlloy;
+use foundry_compilers::info::ContractInfo;
 use foundry_config::{Chain, Config};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
 use std::{
     ffi::OsStr,
     future::Future,
@@ -118,10 +121,47 @@ where
 {
     match chain {
         Some(chain) => Ok(chain),
-        None => Ok(Chain::from_id(provider.get_chainid().await?.as_u64())),
+        None => Ok(Chain::from_id(get_chain_id(&provider).await?)),
     }
 }
 
+/// Queries the chain ID of the provider.
+///
+/// Endpoints should return the chain ID as a hex quantity, but non-standard ones may return a
+/// decimal string or a number instead. A chain ID of zero is rejected.
+pub async fn get_chain_id<M: Middleware>(provider: &M) -> Result<u64> {
+    let chain_id: RawChainId = provider.provider().request("eth_chainId", ()).await?;
+    parse_chain_id(&chain_id)
+}
+
+/// An `eth_chainId` response.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+enum RawChainId {
+    Number(u64),
+    String(String),
+}
+
+fn parse_chain_id(chain_id: &RawChainId) -> Result<u64> {
+    let chain_id = match chain_id {
+        RawChainId::Number(chain_id) => *chain_id,
+        RawChainId::String(chain_id) => {
+            let chain_id = chain_id.trim();
+            let parsed = match chain_id.strip_prefix("0x").or_else(|| chain_id.strip_prefix("0X")) {
+                Some(hex) => u64::from_str_radix(hex, 16),
+                None => chain_id.parse(),
+            };
+            parsed.map_err(|_| {
+                eyre::eyre!("The RPC endpoint returned an invalid chain ID `{chain_id}`")
+            })?
+        }
+    };
+    if chain_id == 0 {
+        eyre::bail!("The RPC endpoint returned chain ID 0")
+    }
+    Ok(chain_id)
+}
+
 /// Parses an ether value from a string.
 ///
 /// The amount can be tagged with a unit, e.g. "1ether".
@@ -139,6 +179,27 @@ pub fn parse_ether_value(value: &str) -> Result<U256> {
     })
 }
 
+/// Parses a contract identifier in the form `(<path>:)?<contractname>`.
+///
+/// The contract name is everything after the last `:`, so paths that contain colons, like Windows
+/// paths `C:\src\Counter.sol:Counter`, are supported.
+pub fn parse_contract_info(value: &str) -> Result<ContractInfo> {
+    let (path, name) = match value.rsplit_once(':') {
+        Some((path, name)) => {
+            if path.trim().is_empty() {
+                eyre::bail!("contract path must not be empty: `{value}`")
+            }
+            (Some(path.trim().to_string()), name)
+        }
+        None => (None, value),
+    };
+    let name = name.trim();
+    if name.is_empty() {
+        eyre::bail!("contract name must not be empty: `{value}`")
+    }
+    Ok(ContractInfo { path, name: name.to_string() })
+}
+
 /// Parses a `Duration` from a &str
 pub fn parse_delay(delay: &str) -> Result<Duration> {
     let delay = if delay.ends_with("ms") {
@@ -161,11 +222,17 @@ pub fn now() -> Duration {
     SystemTime::now().duration_since(UNIX_EPOCH).expect("time went backwards")
 }
 
-/// Runs the `future` in a new [`tokio::runtime::Runtime`]
+/// The [`tokio::runtime::Runtime`] shared by all commands of the process.
+static RUNTIME: Lazy<tokio::runtime::Runtime> =
+    Lazy::new(|| tokio::runtime::Runtime::new().expect("could not start tokio rt"));
+
+/// Runs the `future` on the shared [`tokio::runtime::Runtime`]
+///
+/// The runtime is created once, so commands that run several futures don't each start and tear
+/// down an executor.
 #[allow(unused)]
 pub fn block_on<F: Future>(future: F) -> F::Output {
-    let rt = tokio::runtime::Runtime::new().expect("could not start tokio rt");
-    rt.block_on(future)
+    RUNTIME.block_on(future)
 }
 
 /// Conditionally print a message
@@ -640,6 +707,37 @@ mod tests {
     use std::{env, fs::File, io::Write};
     use tempfile::tempdir;
 
+    #[test]
+    fn can_parse_contract_info() {
+        let info = parse_contract_info("src/Counter.sol:Counter").unwrap();
+        assert_eq!(info.path.as_deref(), Some("src/Counter.sol"));
+        assert_eq!(info.name, "Counter");
+
+        let info = parse_contract_info(r"C:\src\Counter.sol:Counter").unwrap();
+        assert_eq!(info.path.as_deref(), Some(r"C:\src\Counter.sol"));
+        assert_eq!(info.name, "Counter");
+
+        let info = parse_contract_info("Counter").unwrap();
+        assert_eq!(info.path, None);
+        assert_eq!(info.name, "Counter");
+
+        assert!(parse_contract_info("src/Counter.sol:").is_err());
+        assert!(parse_contract_info(":Counter").is_err());
+    }
+
+    #[test]
+    fn can_parse_chain_id() {
+        assert_eq!(parse_chain_id(&RawChainId::String("0x1".into())).unwrap(), 1);
+        assert_eq!(parse_chain_id(&RawChainId::String("0xaa36a7".into())).unwrap(), 11155111);
+        assert_eq!(parse_chain_id(&RawChainId::String("137".into())).unwrap(), 137);
+        assert_eq!(parse_chain_id(&RawChainId::Number(10)).unwrap(), 10);
+
+        let err = parse_chain_id(&RawChainId::String("0x0".into())).unwrap_err();
+        assert_eq!(err.to_string(), "The RPC endpoint returned chain ID 0");
+        let err = parse_chain_id(&RawChainId::String("mainnet".into())).unwrap_err();
+        assert_eq!(err.to_string(), "The RPC endpoint returned an invalid chain ID `mainnet`");
+    }
+
     #[test]
     fn foundry_path_ext_works() {
         let p = Path::new("contracts/MyTest.t.sol");
@@ -676,4 +774,18 @@ mod tests {
         assert_eq!(env::var("TESTCWDKEY").unwrap(), "cwd_val");
         assert_eq!(env::var("TESTPRJKEY").unwrap(), "prj_val");
     }
+
+    #[test]
+    fn can_read_constructor_args_file_with_comments() {
+        let temp = tempdir().unwrap();
+        let path = temp.path().join("args.txt");
+        fs::write(
+            &path,
+            "# owner\n0xd8509bee9c9bf012282ad33aba0d87241baf5064\n  # supply and name\n1000 \"Token\"\n",
+        )
+        .unwrap();
+
+        let args = read_constructor_args_file(path).unwrap();
+        assert_eq!(args, vec!["0xd8509bee9c9bf012282ad33aba0d87241baf5064", "1000", "\"Token\""]);
+    }
 }
@@ -1,7 +1,7 @@
 //! ABI related helper functions.
 
 use alloy_dyn_abi::{DynSolType, DynSolValue, FunctionExt, JsonAbiExt};
-use alloy_json_abi::{Event, Function};
+use alloy_json_abi::{Event, Function, Param};
 use alloy_primitives::{hex, Address, LogData};
 use eyre::{Context, ContextCompat, Result};
 use foundry_block_explorers::{contract::ContractMetadata, errors::EtherscanError, Client};
@@ -21,7 +21,64 @@ where
     func.abi_encode_input(params.as_slice()).map_err(Into::into)
 }
 
+/// Given a list of params and a vector of string arguments, it proceeds to convert the args to
+/// alloy [DynSolValue]s and then ABI encode them, without a function selector.
+///
+/// This is useful for encoding constructor arguments.
+pub fn encode_args<I, S>(inputs: &[Param], args: I) -> Result<Vec<u8>>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let args = args.into_iter().collect::<Vec<_>>();
+    if inputs.len() != args.len() {
+        eyre::bail!("expected {} args, got {}", inputs.len(), args.len())
+    }
+    let params = std::iter::zip(inputs, args)
+        .map(|(input, arg)| coerce_value(&input.selector_type(), arg.as_ref()))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(DynSolValue::Tuple(params).abi_encode_params())
+}
+
+/// Given a list of params and a JSON array of arguments, it proceeds to convert the args to alloy
+/// [DynSolValue]s and then ABI encode them, without a function selector.
+///
+/// Unlike [encode_args], arrays and tuples are passed as nested JSON arrays, e.g.
+/// `[[1, 2], ["0x..", true]]` for `(uint256[], (address, bool))`.
+pub fn encode_args_json(inputs: &[Param], args: &serde_json::Value) -> Result<Vec<u8>> {
+    let args = args.as_array().wrap_err("arguments must be a JSON array")?;
+    if inputs.len() != args.len() {
+        eyre::bail!("expected {} args, got {}", inputs.len(), args.len())
+    }
+    let params = std::iter::zip(inputs, args)
+        .map(|(input, arg)| coerce_json_value(&DynSolType::parse(&input.selector_type())?, arg))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(DynSolValue::Tuple(params).abi_encode_params())
+}
+
+/// Given a function and a vector of string arguments, it proceeds to convert the args to alloy
+/// [DynSolValue]s and then encodes them in the non-standard packed mode, as done by
+/// `abi.encodePacked`.
+pub fn encode_function_args_packed<I, S>(func: &Function, args: I) -> Result<Vec<u8>>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let args = args.into_iter().collect::<Vec<_>>();
+    if func.inputs.len() != args.len() {
+        eyre::bail!("expected {} args, got {}", func.inputs.len(), args.len())
+    }
+    let params = std::iter::zip(&func.inputs, args)
+        .map(|(input, arg)| coerce_value(&input.selector_type(), arg.as_ref()))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(DynSolValue::Tuple(params).abi_encode_packed())
+}
+
 /// Decodes the calldata of the function
+///
+/// If `fn_selector` is set, the first 4 bytes of the input calldata are stripped as the function
+/// selector. Data without selector, e.g. constructor arguments, must be decoded without
+/// `fn_selector`.
 pub fn abi_decode_calldata(
     sig: &str,
     calldata: &str,
@@ -182,6 +239,41 @@ fn coerce_value(ty: &str, arg: &str) -> Result<DynSolValue> {
     Ok(DynSolType::coerce_str(&ty, arg)?)
 }
 
+/// Helper function to coerce a JSON value to a [DynSolValue] of the given type
+///
+/// Numbers that don't fit into an `f64` must be passed as strings.
+fn coerce_json_value(ty: &DynSolType, value: &serde_json::Value) -> Result<DynSolValue> {
+    use serde_json::Value;
+
+    match (ty, value) {
+        (DynSolType::Array(inner), Value::Array(values)) => {
+            let values = values.iter().map(|value| coerce_json_value(inner, value));
+            Ok(DynSolValue::Array(values.collect::<Result<_>>()?))
+        }
+        (DynSolType::FixedArray(inner, len), Value::Array(values)) => {
+            if *len != values.len() {
+                eyre::bail!("expected {len} elements for `{ty}`, got {}", values.len())
+            }
+            let values = values.iter().map(|value| coerce_json_value(inner, value));
+            Ok(DynSolValue::FixedArray(values.collect::<Result<_>>()?))
+        }
+        (DynSolType::Tuple(types), Value::Array(values)) => {
+            if types.len() != values.len() {
+                eyre::bail!("expected {} elements for `{ty}`, got {}", types.len(), values.len())
+            }
+            let values =
+                std::iter::zip(types, values).map(|(ty, value)| coerce_json_value(ty, value));
+            Ok(DynSolValue::Tuple(values.collect::<Result<_>>()?))
+        }
+        (DynSolType::Array(_) | DynSolType::FixedArray(..) | DynSolType::Tuple(_), _) => {
+            eyre::bail!("expected a JSON array for `{ty}`, got `{value}`")
+        }
+        (_, Value::String(value)) => Ok(ty.coerce_str(value)?),
+        (_, Value::Number(_) | Value::Bool(_)) => Ok(ty.coerce_str(&value.to_string())?),
+        _ => eyre::bail!("cannot convert `{value}` to `{ty}`"),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -209,6 +301,70 @@ mod tests {
         assert_eq!(func.outputs[0].ty, "bytes4");
     }
 
+    #[test]
+    fn test_encode_args() {
+        let func = get_func("constructor(uint256 a, address b)").unwrap();
+        let args = ["1", "0x0000000000000000000000000000000000000002"];
+        let encoded = encode_args(&func.inputs, args).unwrap();
+        assert_eq!(encoded, encode_function_args(&func, args).unwrap()[4..]);
+
+        let err = encode_args(&func.inputs, ["1"]).unwrap_err();
+        assert_eq!(err.to_string(), "expected 2 args, got 1");
+    }
+
+    #[test]
+    fn test_encode_args_json() {
+        let func = get_func("constructor(uint256[] a, (address, bool) b, string c)").unwrap();
+        let args = serde_json::json!([
+            [1, "2"],
+            ["0x0000000000000000000000000000000000000002", true],
+            "abc"
+        ]);
+        let encoded = encode_args_json(&func.inputs, &args).unwrap();
+        let expected = encode_args(
+            &func.inputs,
+            ["[1,2]", "(0x0000000000000000000000000000000000000002,true)", "abc"],
+        )
+        .unwrap();
+        assert_eq!(encoded, expected);
+
+        let err = encode_args_json(&func.inputs, &serde_json::json!([1, [], "abc"])).unwrap_err();
+        assert_eq!(err.to_string(), "expected a JSON array for `uint256[]`, got `1`");
+
+        let err =
+            encode_args_json(&func.inputs, &serde_json::json!([[], [true], "abc"])).unwrap_err();
+        assert_eq!(err.to_string(), "expected 2 elements for `(address,bool)`, got 1");
+    }
+
+    #[test]
+    fn test_encode_function_args_packed() {
+        let func = get_func("f(uint8 a, address b, string c)").unwrap();
+        let args = ["1", "0x0000000000000000000000000000000000000002", "abc"];
+        let encoded = encode_function_args_packed(&func, args).unwrap();
+        assert_eq!(hex::encode(encoded), "010000000000000000000000000000000000000002616263");
+
+        let err = encode_function_args_packed(&func, ["1"]).unwrap_err();
+        assert_eq!(err.to_string(), "expected 3 args, got 1");
+    }
+
+    #[test]
+    fn test_decode_calldata_selector() {
+        let args = "0000000000000000000000000000000000000000000000000000000000000001";
+        let expected = vec![DynSolValue::Uint(U256::from(1), 256)];
+
+        // data without selector, e.g. constructor arguments
+        assert_eq!(abi_decode_calldata("f(uint256)", args, true, false).unwrap(), expected);
+        assert_eq!(
+            abi_decode_calldata("f(uint256)", &format!("b3de648b{args}"), true, true).unwrap(),
+            expected
+        );
+        // the selector is stripped even if the arguments aren't 32-byte aligned
+        assert_eq!(
+            abi_decode_calldata("f(uint256)", &format!("b3de648b{args}00"), true, true).unwrap(),
+            expected
+        );
+    }
+
     #[test]
     fn test_indexed_only_address() {
         let event = get_event("event Ev(address,uint256,address)").unwrap();
@@ -36,6 +36,9 @@ pub struct ProjectCompiler {
     /// Whether to also print contract sizes.
     print_sizes: Option<bool>,
 
+    /// Whether to exit with an error if a contract exceeds the size limit.
+    fail_on_size_limit: Option<bool>,
+
     /// Whether to print anything at all. Overrides other `print` options.
     quiet: Option<bool>,
 
@@ -64,6 +67,7 @@ impl ProjectCompiler {
             verify: None,
             print_names: None,
             print_sizes: None,
+            fail_on_size_limit: None,
             quiet: Some(crate::shell::verbosity().is_silent()),
             bail: None,
             filter: None,
@@ -92,6 +96,15 @@ impl ProjectCompiler {
         self
     }
 
+    /// Sets whether to exit with an error if a contract exceeds the size limit.
+    ///
+    /// This prints the contract sizes as well.
+    #[inline]
+    pub fn fail_on_size_limit(mut self, yes: bool) -> Self {
+        self.fail_on_size_limit = Some(yes);
+        self
+    }
+
     /// Sets whether to print anything at all. Overrides other `print` options.
     #[inline]
     #[doc(alias = "silent")]
@@ -206,6 +219,9 @@ impl ProjectCompiler {
             }
 
             self.handle_output(&output);
+        } else if self.fail_on_size_limit.unwrap_or(false) {
+            // nothing is printed, but the size limit is still enforced
+            check_size_limit(&size_report(&output));
         }
 
         Ok(output)
@@ -214,7 +230,8 @@ impl ProjectCompiler {
     /// If configured, this will print sizes or names
     fn handle_output(&self, output: &ProjectCompileOutput) {
         let print_names = self.print_names.unwrap_or(false);
-        let print_sizes = self.print_sizes.unwrap_or(false);
+        let fail_on_size_limit = self.fail_on_size_limit.unwrap_or(false);
+        let print_sizes = self.print_sizes.unwrap_or(false) || fail_on_size_limit;
 
         // print any sizes or names
         if print_names {
@@ -239,35 +256,43 @@ impl ProjectCompiler {
                 println!();
             }
 
-            let mut size_report = SizeReport { contracts: BTreeMap::new() };
-            let artifacts: BTreeMap<_, _> = output.artifacts().collect();
-            for (name, artifact) in artifacts {
-                let size = deployed_contract_size(artifact).unwrap_or_default();
-
-                let dev_functions =
-                    artifact.abi.as_ref().map(|abi| abi.functions()).into_iter().flatten().filter(
-                        |func| {
-                            func.name.is_test() ||
-                                func.name.eq("IS_TEST") ||
-                                func.name.eq("IS_SCRIPT")
-                        },
-                    );
-
-                let is_dev_contract = dev_functions.count() > 0;
-                size_report.contracts.insert(name, ContractInfo { size, is_dev_contract });
-            }
-
+            let size_report = size_report(output);
             println!("{size_report}");
 
-            // TODO: avoid process::exit
-            // exit with error if any contract exceeds the size limit, excluding test contracts.
-            if size_report.exceeds_size_limit() {
-                std::process::exit(1);
-            }
+            check_size_limit(&size_report);
         }
     }
 }
 
+/// Collects the deployed sizes of all artifacts in the output.
+fn size_report(output: &ProjectCompileOutput) -> SizeReport {
+    let mut size_report = SizeReport { contracts: BTreeMap::new() };
+    let artifacts: BTreeMap<_, _> = output.artifacts().collect();
+    for (name, artifact) in artifacts {
+        let size = deployed_contract_size(artifact).unwrap_or_default();
+
+        let dev_functions =
+            artifact.abi.as_ref().map(|abi| abi.functions()).into_iter().flatten().filter(|func| {
+                func.name.is_test() || func.name.eq("IS_TEST") || func.name.eq("IS_SCRIPT")
+            });
+
+        let is_dev_contract = dev_functions.count() > 0;
+        size_report.contracts.insert(name, ContractInfo { size, is_dev_contract });
+    }
+    size_report
+}
+
+/// Exits with an error if any contract exceeds the size limit, excluding test contracts.
+fn check_size_limit(size_report: &SizeReport) {
+    if size_report.exceeds_size_limit() {
+        // TODO: avoid process::exit
+        eprintln!(
+            "Error: some contracts exceed the EIP-170 size limit of {CONTRACT_SIZE_LIMIT} bytes"
+        );
+        std::process::exit(1);
+    }
+}
+
 /// Map over artifacts contract sources name -> file_id -> (source, contract)
 #[derive(Clone, Debug, Default)]
 pub struct ContractSources(pub HashMap<String, HashMap<u32, (String, ContractBytecodeSome)>>);
@@ -309,7 +334,13 @@ impl Display for SizeReport {
             Cell::new("Margin (kB)").add_attribute(Attribute::Bold).fg(Color::Blue),
         ]);
 
-        let contracts = self.contracts.iter().filter(|(_, c)| !c.is_dev_contract && c.size > 0);
+        // largest contracts first
+        let mut contracts = self
+            .contracts
+            .iter()
+            .filter(|(_, c)| !c.is_dev_contract && c.size > 0)
+            .collect::<Vec<_>>();
+        contracts.sort_by(|(_, a), (_, b)| b.size.cmp(&a.size));
         for (name, contract) in contracts {
             let margin = CONTRACT_SIZE_LIMIT as isize - contract.size as isize;
             let color = match contract.size {
@@ -552,4 +583,27 @@ mod tests {
         let file = Path::new("/home/script/Contract.sol");
         assert!(!SkipBuildFilter::Custom("*/script/**".to_string()).is_match(file));
     }
+
+    #[test]
+    fn test_size_report() {
+        let contract = |size, is_dev_contract| ContractInfo { size, is_dev_contract };
+        let size_report = SizeReport {
+            contracts: BTreeMap::from([
+                ("Counter".to_string(), contract(1000, false)),
+                ("CounterTest".to_string(), contract(30000, true)),
+                ("Token".to_string(), contract(2000, false)),
+            ]),
+        };
+        assert_eq!(size_report.max_size(), 2000);
+        assert!(!size_report.exceeds_size_limit());
+
+        // sorted by size, excluding test contracts
+        let table = size_report.to_string();
+        assert!(!table.contains("CounterTest"));
+        assert!(table.find("Token").unwrap() < table.find("Counter").unwrap());
+
+        let mut size_report = size_report;
+        size_report.contracts.insert("Large".to_string(), contract(CONTRACT_SIZE_LIMIT + 1, false));
+        assert!(size_report.exceeds_size_limit());
+    }
 }
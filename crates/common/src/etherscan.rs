@@ -0,0 +1,69 @@
+//! Etherscan API endpoints that aren't covered by the block explorer [`Client`].
+
+use alloy_primitives::{Address, B256};
+use eyre::{eyre, Result};
+use foundry_block_explorers::Client;
+use serde::Deserialize;
+
+/// Response of etherscan's `getcontractcreation` endpoint.
+#[derive(Debug, Deserialize)]
+struct ContractCreationResponse {
+    status: String,
+    result: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ContractCreationData {
+    tx_hash: B256,
+}
+
+/// Queries the etherscan API for the hash of the transaction that created the contract at
+/// `address`.
+pub async fn contract_creation_tx(
+    client: &Client,
+    api_key: &str,
+    address: Address,
+) -> Result<B256> {
+    // the query of the API URL is kept, it selects the chain of the v2 API
+    let mut url = client.etherscan_api_url().clone();
+    url.query_pairs_mut()
+        .append_pair("module", "contract")
+        .append_pair("action", "getcontractcreation")
+        .append_pair("contractaddresses", &address.to_string())
+        .append_pair("apikey", api_key);
+
+    let body = reqwest::get(url).await?.text().await?;
+    let resp: ContractCreationResponse = serde_json::from_str(&body)?;
+    trace!(?resp, "Received contract creation response");
+    if resp.status != "1" {
+        eyre::bail!("Failed to fetch the contract creation transaction: {}", resp.result)
+    }
+
+    let data: Vec<ContractCreationData> = serde_json::from_value(resp.result)?;
+    data.into_iter()
+        .next()
+        .map(|data| data.tx_hash)
+        .ok_or_else(|| eyre!("No creation transaction found for {address}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_contract_creation_response() {
+        let resp: ContractCreationResponse = serde_json::from_str(
+            r#"{"status":"1","message":"OK","result":[{"contractAddress":"0xb4c79dab8f259c7aee6e5b2aa729821864227e84","contractCreator":"0x2c1ba59d6f58433fb1eaee7d20b26ed83bda51a3","txHash":"0x2696c3ffa1e35b4b2c452a5a1f6d6b5b12e231ec1b2c8c2ddc1a61d77fc0fdc3"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(resp.status, "1");
+        let data: Vec<ContractCreationData> = serde_json::from_value(resp.result).unwrap();
+        assert_eq!(
+            data[0].tx_hash,
+            "0x2696c3ffa1e35b4b2c452a5a1f6d6b5b12e231ec1b2c8c2ddc1a61d77fc0fdc3"
+                .parse::<B256>()
+                .unwrap()
+        );
+    }
+}
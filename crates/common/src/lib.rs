@@ -13,6 +13,7 @@ pub mod compile;
 pub mod constants;
 pub mod contracts;
 pub mod errors;
+pub mod etherscan;
 pub mod evm;
 pub mod fmt;
 pub mod fs;
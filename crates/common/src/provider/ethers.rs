@@ -68,6 +68,9 @@ pub struct ProviderBuilder {
     headers: Vec<String>,
 }
 
+/// The URL schemes of the RPC endpoints [RuntimeClient] can connect to.
+const SUPPORTED_URL_SCHEMES: &[&str] = &["http", "https", "ws", "wss", "file"];
+
 // === impl ProviderBuilder ===
 
 impl ProviderBuilder {
@@ -225,6 +228,13 @@ impl ProviderBuilder {
             headers,
         } = self;
         let url = url?;
+        // fail early instead of on the first request
+        if !SUPPORTED_URL_SCHEMES.contains(&url.scheme()) {
+            eyre::bail!(
+                "unsupported RPC URL scheme `{}` in {url}, expected one of http, https, ws, wss or a path to an IPC socket",
+                url.scheme()
+            )
+        }
 
         let client_builder = RuntimeClientBuilder::new(
             url.clone(),
@@ -320,4 +330,14 @@ mod tests {
         let url = builder.url.unwrap();
         assert_eq!(url, Url::parse("http://localhost:8545").unwrap());
     }
+
+    #[test]
+    fn rejects_unsupported_url_scheme() {
+        for url in ["http://localhost:8545", "wss://eth.llamarpc.com"] {
+            assert!(ProviderBuilder::new(url).build().is_ok());
+        }
+
+        let err = ProviderBuilder::new("ftp://localhost:8545").build().unwrap_err();
+        assert!(err.to_string().starts_with("unsupported RPC URL scheme `ftp`"));
+    }
 }
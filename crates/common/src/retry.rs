@@ -1,8 +1,23 @@
 //! Retry utilities.
 
 use eyre::{Error, Result};
+use rand::Rng;
 use std::{future::Future, time::Duration};
 
+/// The maximum delay between two attempts when backing off exponentially.
+pub const MAX_BACKOFF_DELAY: Duration = Duration::from_secs(60);
+
+/// Error type for [Retry::run_async_until_break].
+#[derive(Debug)]
+pub enum RetryError<E = Error> {
+    /// The attempt failed and should be retried after the configured delay.
+    Retry(E),
+    /// The attempt was rate limited and should be retried with an exponentially growing delay.
+    RateLimited(E),
+    /// The attempt failed and should not be retried.
+    Break(E),
+}
+
 /// A type that keeps track of attempts.
 #[derive(Clone, Debug)]
 pub struct Retry {
@@ -51,8 +66,85 @@ impl Retry {
         }
     }
 
+    /// Runs the given async closure in a loop, retrying if it fails up to the specified number of
+    /// times.
+    ///
+    /// Unlike [Retry::run_async], the closure decides whether an error is retried:
+    /// [RetryError::Break] returns the error immediately and [RetryError::RateLimited] backs off
+    /// exponentially, with jitter, up to [MAX_BACKOFF_DELAY].
+    pub async fn run_async_until_break<F, Fut, T>(mut self, mut callback: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, RetryError>>,
+    {
+        let mut rate_limited = 0;
+        loop {
+            match callback().await {
+                Ok(t) => return Ok(t),
+                Err(RetryError::Retry(e)) if self.retries > 0 => {
+                    self.handle_err(e);
+                    if let Some(delay) = self.delay {
+                        tokio::time::sleep(delay).await;
+                    }
+                }
+                Err(RetryError::RateLimited(e)) if self.retries > 0 => {
+                    self.handle_err(e);
+                    tokio::time::sleep(self.backoff_delay(rate_limited)).await;
+                    rate_limited += 1;
+                }
+                Err(RetryError::Retry(e) | RetryError::RateLimited(e) | RetryError::Break(e)) => {
+                    return Err(e)
+                }
+            }
+        }
+    }
+
+    /// Returns the delay before the next attempt after `attempt` consecutive rate limited
+    /// attempts.
+    fn backoff_delay(&self, attempt: u32) -> Duration {
+        let base = self.delay.unwrap_or_default().max(Duration::from_secs(1));
+        let delay = base.saturating_mul(2u32.saturating_pow(attempt)).min(MAX_BACKOFF_DELAY);
+        let jitter = rand::thread_rng().gen_range(0..=delay.as_millis() as u64 / 4);
+        (delay + Duration::from_millis(jitter)).min(MAX_BACKOFF_DELAY)
+    }
+
     fn handle_err(&mut self, err: Error) {
         self.retries -= 1;
         warn!("erroneous attempt ({} tries remaining): {}", self.retries, err.root_cause());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backs_off_exponentially() {
+        let retry = Retry::new(5, Some(Duration::from_secs(2)));
+        let first = retry.backoff_delay(0);
+        assert!(first >= Duration::from_secs(2) && first <= Duration::from_millis(2500));
+        let third = retry.backoff_delay(2);
+        assert!(third >= Duration::from_secs(8) && third <= Duration::from_secs(10));
+        assert_eq!(retry.backoff_delay(10), MAX_BACKOFF_DELAY);
+    }
+
+    #[tokio::test]
+    async fn stops_retrying_on_break() {
+        let mut attempts = 0;
+        let res: Result<()> = Retry::new(5, None)
+            .run_async_until_break(|| {
+                attempts += 1;
+                let attempt = attempts;
+                async move {
+                    if attempt < 3 {
+                        Err(RetryError::Retry(eyre::eyre!("retry")))
+                    } else {
+                        Err(RetryError::Break(eyre::eyre!("break")))
+                    }
+                }
+            })
+            .await;
+        assert_eq!(res.unwrap_err().to_string(), "break");
+        assert_eq!(attempts, 3);
+    }
+}
@@ -61,6 +61,11 @@ pub fn verbosity() -> Verbosity {
     with_shell(|shell| shell.verbosity)
 }
 
+/// Returns the configured verbose level, see [`Shell::verbose_level`]
+pub fn verbose_level() -> u8 {
+    with_shell(|shell| shell.verbose_level)
+}
+
 /// An abstraction around console output that also considers verbosity
 #[derive(Default)]
 pub struct Shell {
@@ -68,6 +73,8 @@ pub struct Shell {
     output: ShellOut,
     /// How to emit messages.
     verbosity: Verbosity,
+    /// The number of `-v` flags passed to the top-level command.
+    verbose_level: u8,
 }
 
 // === impl Shell ===
@@ -75,7 +82,7 @@ pub struct Shell {
 impl Shell {
     /// Creates a new shell instance
     pub fn new(output: ShellOut, verbosity: Verbosity) -> Self {
-        Self { output, verbosity }
+        Self { output, verbosity, verbose_level: 0 }
     }
 
     /// Returns a new shell that conforms to the specified verbosity arguments, where `json` takes
@@ -103,6 +110,20 @@ impl Shell {
         Self::new(Default::default(), verbosity)
     }
 
+    /// Sets the verbose level, i.e. the number of `-v` flags passed to the top-level command
+    pub fn with_verbose_level(mut self, verbose_level: u8) -> Self {
+        self.verbose_level = verbose_level;
+        self
+    }
+
+    /// Returns the verbose level, i.e. the number of `-v` flags passed to the top-level command
+    ///
+    /// Commands print only their result by default, progress is printed at level 1 and details
+    /// useful for debugging at level 2.
+    pub fn verbose_level(&self) -> u8 {
+        self.verbose_level
+    }
+
     /// Write a fragment to stdout
     ///
     /// Caller is responsible for deciding whether [`Shell::verbosity`] is affects output.
@@ -138,12 +159,16 @@ impl Shell {
 impl fmt::Debug for Shell {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.output {
-            ShellOut::Write(_) => {
-                f.debug_struct("Shell").field("verbosity", &self.verbosity).finish()
-            }
-            ShellOut::Stream => {
-                f.debug_struct("Shell").field("verbosity", &self.verbosity).finish()
-            }
+            ShellOut::Write(_) => f
+                .debug_struct("Shell")
+                .field("verbosity", &self.verbosity)
+                .field("verbose_level", &self.verbose_level)
+                .finish(),
+            ShellOut::Stream => f
+                .debug_struct("Shell")
+                .field("verbosity", &self.verbosity)
+                .field("verbose_level", &self.verbose_level)
+                .finish(),
         }
     }
 }
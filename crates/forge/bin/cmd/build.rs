@@ -52,6 +52,15 @@ pub struct BuildArgs {
     #[serde(skip)]
     pub sizes: bool,
 
+    /// Print compiled contract sizes and exit with an error if a contract exceeds the EIP-170
+    /// size limit of 24576 bytes.
+    ///
+    /// Unlike `--sizes`, the size limit is also enforced when nothing is printed, e.g. with
+    /// `--format-json`. Test and script contracts are not checked.
+    #[clap(long)]
+    #[serde(skip)]
+    pub fail_size: bool,
+
     /// Skip building files whose names contain the given filter.
     ///
     /// `test` and `script` are aliases for `.t.sol` and `.s.sol`.
@@ -90,6 +99,7 @@ impl BuildArgs {
         let output = ProjectCompiler::new()
             .print_names(self.names)
             .print_sizes(self.sizes)
+            .fail_on_size_limit(self.fail_size)
             .quiet(self.format_json)
             .bail(!self.format_json)
             .filter(Box::new(SkipBuildFilters(self.skip.unwrap_or_default())))
@@ -15,16 +15,18 @@ use ethers_middleware::MiddlewareBuilder;
 use ethers_providers::Middleware;
 use eyre::{Context, Result};
 use foundry_cli::{
-    opts::{CoreBuildArgs, EthereumOpts, EtherscanOpts, TransactionOpts},
+    opts::{CoreBuildArgs, EthereumOpts, TransactionOpts},
     utils::{self, read_constructor_args_file, remove_contract, LoadConfig},
 };
 use foundry_common::{
     compile::ProjectCompiler,
     fmt::parse_tokens,
     provider::ethers::estimate_eip1559_fees,
+    shell,
     types::{ToAlloy, ToEthers},
 };
 use foundry_compilers::{artifacts::BytecodeObject, info::ContractInfo, utils::canonicalized};
+use semver::Version;
 use serde_json::json;
 use std::{borrow::Borrow, marker::PhantomData, path::PathBuf, sync::Arc};
 
@@ -32,6 +34,7 @@ use std::{borrow::Borrow, marker::PhantomData, path::PathBuf, sync::Arc};
 #[derive(Clone, Debug, Parser)]
 pub struct CreateArgs {
     /// The contract identifier in the form `<path>:<contractname>`.
+    #[clap(value_parser = utils::parse_contract_info)]
     contract: ContractInfo,
 
     /// The constructor arguments.
@@ -119,16 +122,17 @@ impl CreateArgs {
         // Add arguments to constructor
         let config = self.eth.try_load_config_emit_warnings()?;
         let provider = utils::get_provider(&config)?;
+        let constructor_args = if let Some(ref constructor_args_path) = self.constructor_args_path {
+            read_constructor_args_file(constructor_args_path.to_path_buf())?
+        } else {
+            self.constructor_args.clone()
+        };
         let params = match abi.constructor {
-            Some(ref v) => {
-                let constructor_args =
-                    if let Some(ref constructor_args_path) = self.constructor_args_path {
-                        read_constructor_args_file(constructor_args_path.to_path_buf())?
-                    } else {
-                        self.constructor_args.clone()
-                    };
-                self.parse_constructor_args(v, &constructor_args)?
-            }
+            Some(ref v) => self.parse_constructor_args(v, &constructor_args)?,
+            None if !constructor_args.is_empty() => eyre::bail!(
+                "contract has no constructor, got {} constructor args",
+                constructor_args.len()
+            ),
             None => vec![],
         };
 
@@ -156,6 +160,13 @@ impl CreateArgs {
         self.eth.etherscan.chain.map(|chain| chain.id())
     }
 
+    /// Returns the solc version pinned with `--use`, if it's a version rather than a path, so the
+    /// contract is verified with the version it was built with.
+    fn pinned_compiler_version(&self) -> Option<String> {
+        let solc = self.opts.use_solc.as_deref()?.trim_start_matches("solc:");
+        Version::parse(solc.trim_start_matches('v')).is_ok().then(|| solc.to_string())
+    }
+
     /// Ensures the verify command can be executed.
     ///
     /// This is supposed to check any things that might go wrong when preparing a verify request
@@ -169,28 +180,20 @@ impl CreateArgs {
     ) -> Result<()> {
         // NOTE: this does not represent the same `VerifyArgs` that would be sent after deployment,
         // since we don't know the address yet.
-        let mut verify = verify::VerifyArgs {
-            address: Default::default(),
-            contract: self.contract.clone(),
-            compiler_version: None,
-            constructor_args,
-            constructor_args_path: None,
-            num_of_optimizations: None,
-            etherscan: EtherscanOpts {
-                key: self.eth.etherscan.key.clone(),
-                chain: Some(chain.into()),
-            },
-            flatten: false,
-            force: false,
-            skip_is_verified_check: true,
-            watch: true,
-            retry: self.retry,
-            libraries: vec![],
-            root: None,
+        let mut verify = verify::VerifyArgs::from(verify::VerifyParams {
+            chain: Some(chain.into()),
+            etherscan_key: self.eth.etherscan.key.clone(),
+            rpc: self.eth.rpc.clone(),
             verifier: self.verifier.clone(),
+            constructor_args,
+            compiler_version: self.pinned_compiler_version(),
+            evm_version: self.opts.compiler.evm_version,
             via_ir: self.opts.via_ir,
+            skip_is_verified_check: true,
             show_standard_json_input: self.show_standard_json_input,
-        };
+            retry: self.retry,
+            ..verify::VerifyParams::new(Default::default(), self.contract.clone())
+        });
 
         // Check config for Etherscan API Keys to avoid preflight check failing if no
         // ETHERSCAN_API_KEY value set.
@@ -316,31 +319,35 @@ impl CreateArgs {
             return Ok(());
         }
 
-        println!("Starting contract verification...");
+        if shell::verbose_level() > 0 {
+            shell::println("Starting contract verification...")?;
+        }
 
         let num_of_optimizations =
             if self.opts.compiler.optimize { self.opts.compiler.optimizer_runs } else { None };
-        let verify = verify::VerifyArgs {
-            address,
-            contract: self.contract,
-            compiler_version: None,
+        let compiler_version = self.pinned_compiler_version();
+        let verify = verify::VerifyArgs::from(verify::VerifyParams {
+            chain: Some(chain.into()),
+            etherscan_key: self.eth.etherscan.key,
+            rpc: self.eth.rpc,
+            verifier: self.verifier,
             constructor_args,
-            constructor_args_path: None,
+            compiler_version,
+            evm_version: self.opts.compiler.evm_version,
             num_of_optimizations,
-            etherscan: EtherscanOpts { key: self.eth.etherscan.key, chain: Some(chain.into()) },
-            flatten: false,
-            force: false,
-            skip_is_verified_check: false,
-            watch: true,
-            retry: self.retry,
-            libraries: vec![],
-            root: None,
-            verifier: self.verifier,
             via_ir: self.opts.via_ir,
             show_standard_json_input: self.show_standard_json_input,
-        };
-        println!("Waiting for {} to detect contract deployment...", verify.verifier.verifier);
-        verify.run().await
+            retry: self.retry,
+            ..verify::VerifyParams::new(address, self.contract)
+        });
+        if shell::verbose_level() > 0 {
+            shell::println(format!(
+                "Waiting for {} to detect contract deployment...",
+                verify.verifier.verifier()
+            ))?;
+        }
+        verify.run().await?;
+        Ok(())
     }
 
     /// Parses the given constructor arguments into a vector of `DynSolValue`s, by matching them
@@ -352,6 +359,14 @@ impl CreateArgs {
         constructor: &Constructor,
         constructor_args: &[String],
     ) -> Result<Vec<DynSolValue>> {
+        if constructor.inputs.len() != constructor_args.len() {
+            eyre::bail!(
+                "constructor expects {} args, got {}",
+                constructor.inputs.len(),
+                constructor_args.len()
+            )
+        }
+
         let mut params = Vec::with_capacity(constructor.inputs.len());
         for (input, arg) in constructor.inputs.iter().zip(constructor_args) {
             // resolve the input type directly
@@ -624,6 +639,17 @@ mod tests {
         assert_eq!(args.chain_id(), Some(9999));
     }
 
+    #[test]
+    fn verifies_with_pinned_solc_version() {
+        let parse = |solc: &str| {
+            CreateArgs::parse_from(["foundry-cli", "src/Domains.sol:Domains", "--use", solc])
+                .pinned_compiler_version()
+        };
+        assert_eq!(parse("0.8.19").as_deref(), Some("0.8.19"));
+        assert_eq!(parse("solc:0.8.19").as_deref(), Some("0.8.19"));
+        assert_eq!(parse("/usr/bin/solc"), None);
+    }
+
     #[test]
     fn test_parse_constructor_args() {
         let args: CreateArgs = CreateArgs::parse_from([
@@ -637,6 +663,20 @@ mod tests {
         assert_eq!(params, vec![DynSolValue::String("Hello".to_string())]);
     }
 
+    #[test]
+    fn test_parse_constructor_args_arity() {
+        let args: CreateArgs = CreateArgs::parse_from([
+            "foundry-cli",
+            "src/Domains.sol:Domains",
+            "--constructor-args",
+            "Hello",
+            "World",
+        ]);
+        let constructor: Constructor = serde_json::from_str(r#"{"type":"constructor","inputs":[{"name":"_name","type":"string","internalType":"string"}],"stateMutability":"nonpayable"}"#).unwrap();
+        let err = args.parse_constructor_args(&constructor, &args.constructor_args).unwrap_err();
+        assert_eq!(err.to_string(), "constructor expects 1 args, got 2");
+    }
+
     #[test]
     fn test_parse_tuple_constructor_args() {
         let args: CreateArgs = CreateArgs::parse_from([
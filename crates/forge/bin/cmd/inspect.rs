@@ -1,7 +1,10 @@
 use clap::Parser;
 use comfy_table::{presets::ASCII_MARKDOWN, Table};
 use eyre::Result;
-use foundry_cli::opts::{CompilerArgs, CoreBuildArgs};
+use foundry_cli::{
+    opts::{CompilerArgs, CoreBuildArgs},
+    utils::parse_contract_info,
+};
 use foundry_common::compile::ProjectCompiler;
 use foundry_compilers::{
     artifacts::{
@@ -20,6 +23,7 @@ use std::fmt;
 #[derive(Clone, Debug, Parser)]
 pub struct InspectArgs {
     /// The identifier of the contract to inspect in the form `(<path>:)?<contractname>`.
+    #[clap(value_parser = parse_contract_info)]
     pub contract: ContractInfo,
 
     /// The contract artifact field to inspect.
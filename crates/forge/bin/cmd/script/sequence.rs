@@ -5,7 +5,7 @@ use crate::cmd::{
         transaction::{wrapper, AdditionalContract, TransactionWithMetadata},
         verify::VerifyBundle,
     },
-    verify::provider::VerificationProviderType,
+    verify::{provider::VerificationProviderType, verify_contract},
 };
 use alloy_primitives::{Address, TxHash};
 use ethers_core::types::{transaction::eip2718::TypedTransaction, TransactionReceipt};
@@ -248,7 +248,7 @@ impl ScriptSequence {
     /// Checks that there is an Etherscan key for the chain id of this sequence.
     pub fn verify_preflight_check(&self, config: &Config, verify: &VerifyBundle) -> Result<()> {
         if config.get_etherscan_api_key(Some(self.chain.into())).is_none() &&
-            verify.verifier.verifier == VerificationProviderType::Etherscan
+            verify.verifier.verifier() == VerificationProviderType::Etherscan
         {
             eyre::bail!(
                 "Etherscan API key wasn't found for chain id {}. On-chain execution aborted",
@@ -271,7 +271,7 @@ impl ScriptSequence {
         verify.set_chain(config, self.chain.into());
 
         if verify.etherscan.key.is_some() ||
-            verify.verifier.verifier != VerificationProviderType::Etherscan
+            verify.verifier.verifier() != VerificationProviderType::Etherscan
         {
             trace!(target: "script", "prepare future verifications");
 
@@ -295,7 +295,7 @@ impl ScriptSequence {
                     (receipt.contract_address.map(|h| h.to_alloy()), tx.typed_tx().data())
                 {
                     match verify.get_verify_args(address, offset, &data.0, &self.libraries) {
-                        Some(verify) => future_verifications.push(verify.run()),
+                        Some(verify) => future_verifications.push(verify_contract(verify)),
                         None => unverifiable_contracts.push(address),
                     };
                 }
@@ -303,7 +303,7 @@ impl ScriptSequence {
                 // Verify potential contracts created during the transaction execution
                 for AdditionalContract { address, init_code, .. } in &tx.additional_contracts {
                     match verify.get_verify_args(*address, 0, init_code, &self.libraries) {
-                        Some(verify) => future_verifications.push(verify.run()),
+                        Some(verify) => future_verifications.push(verify_contract(verify)),
                         None => unverifiable_contracts.push(*address),
                     };
                 }
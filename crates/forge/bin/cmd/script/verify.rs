@@ -1,6 +1,6 @@
 use crate::cmd::{
     retry::RetryArgs,
-    verify::{VerifierArgs, VerifyArgs},
+    verify::{VerifierArgs, VerifyParams},
 };
 use alloy_primitives::Address;
 use foundry_cli::opts::{EtherscanOpts, ProjectPathsArgs};
@@ -66,7 +66,7 @@ impl VerifyBundle {
         self.etherscan.chain = Some(chain);
     }
 
-    /// Given a `VerifyBundle` and contract details, it tries to generate a valid `VerifyArgs` to
+    /// Given a `VerifyBundle` and contract details, it tries to generate valid `VerifyParams` to
     /// use against the `contract_address`.
     pub fn get_verify_args(
         &self,
@@ -74,7 +74,7 @@ impl VerifyBundle {
         create2_offset: usize,
         data: &[u8],
         libraries: &[String],
-    ) -> Option<VerifyArgs> {
+    ) -> Option<VerifyParams> {
         for (artifact, (_contract, bytecode)) in self.known_contracts.iter() {
             // If it's a CREATE2, the tx.data comes with a 32-byte salt in the beginning
             // of the transaction
@@ -97,24 +97,18 @@ impl VerifyBundle {
                     artifact.version.patch,
                 );
 
-                let verify = VerifyArgs {
-                    address: contract_address,
-                    contract,
-                    compiler_version: Some(version.to_string()),
+                let verify = VerifyParams {
+                    chain: self.etherscan.chain,
+                    etherscan_key: self.etherscan.key.clone(),
+                    verifier: self.verifier.clone(),
                     constructor_args: Some(hex::encode(constructor_args)),
-                    constructor_args_path: None,
+                    compiler_version: Some(version.to_string()),
                     num_of_optimizations: self.num_of_optimizations,
-                    etherscan: self.etherscan.clone(),
-                    flatten: false,
-                    force: false,
+                    via_ir: self.via_ir,
+                    libraries: libraries.to_vec(),
                     skip_is_verified_check: true,
-                    watch: true,
                     retry: self.retry,
-                    libraries: libraries.to_vec(),
-                    root: None,
-                    verifier: self.verifier.clone(),
-                    via_ir: self.via_ir,
-                    show_standard_json_input: false,
+                    ..VerifyParams::new(contract_address, contract)
                 };
 
                 return Some(verify)
@@ -3,7 +3,7 @@ use comfy_table::Table;
 use eyre::Result;
 use foundry_cli::{
     opts::{CompilerArgs, CoreBuildArgs, ProjectPathsArgs},
-    utils::FoundryPathExt,
+    utils::{parse_contract_info, FoundryPathExt},
 };
 use foundry_common::{
     compile::ProjectCompiler,
@@ -20,10 +20,12 @@ pub enum SelectorsSubcommands {
     Collision {
         /// The first of the two contracts for which to look selector collisions for, in the form
         /// `(<path>:)?<contractname>`.
+        #[clap(value_parser = parse_contract_info)]
         first_contract: ContractInfo,
 
         /// The second of the two contracts for which to look selector collisions for, in the form
         /// `(<path>:)?<contractname>`.
+        #[clap(value_parser = parse_contract_info)]
         second_contract: ContractInfo,
 
         #[clap(flatten)]
@@ -0,0 +1,250 @@
+use super::{etherscan::EtherscanClients, VerifierArgs, VerifyArgs, VerifyCheckArgs};
+use crate::cmd::retry::RetryArgs;
+use clap::{CommandFactory, FromArgMatches, Parser, ValueHint};
+use eyre::{Context, Result};
+use foundry_cli::{opts::EtherscanOpts, utils::LoadConfig};
+use std::{iter, path::PathBuf};
+
+/// CLI arguments for `forge verify-batch`.
+#[derive(Clone, Debug, Parser)]
+pub struct VerifyBatchArgs {
+    /// The path to a file mapping the deployed contracts.
+    ///
+    /// Every line has the form `<address> <path>:<contractname> [ARGS...]`, where `ARGS` are
+    /// additional `forge verify-contract` arguments for this contract only. Empty lines and lines
+    /// starting with `#` are ignored.
+    #[clap(value_hint = ValueHint::FilePath, value_name = "PATH")]
+    pub path: PathBuf,
+
+    /// The `forge verify-contract` arguments used for every contract, e.g. `-- --chain sepolia`.
+    #[clap(last = true, value_name = "ARGS")]
+    pub args: Vec<String>,
+}
+
+impl VerifyBatchArgs {
+    /// Submits all contracts of the mapping file for verification, one after another, and prints
+    /// a summary of the results.
+    pub async fn run(self) -> Result<()> {
+        let content = foundry_common::fs::read_to_string(&self.path)?;
+        let entries = parse_entries(&content, &self.args)
+            .wrap_err_with(|| format!("Invalid mapping file {}", self.path.display()))?;
+
+        // entries for the same explorer share its client
+        let clients = EtherscanClients::default();
+        let total = entries.len();
+        let mut results = Vec::with_capacity(total);
+        for (id, mut args) in entries {
+            args.etherscan_clients = clients.clone();
+            let result = args.run().await;
+            if let Err(err) = &result {
+                eprintln!("Failed to verify {id}: {err:#}");
+            }
+            results.push((id, result));
+        }
+
+        println!("\nVerification summary:");
+        let mut failed = 0;
+        for (id, result) in &results {
+            match result {
+                Ok(response) if response.already_verified => {
+                    println!("  [Pass] {id} (already verified)")
+                }
+                Ok(_) => println!("  [Pass] {id}"),
+                Err(err) => {
+                    failed += 1;
+                    println!("  [Fail] {id}: {err}");
+                }
+            }
+        }
+
+        if failed > 0 {
+            eyre::bail!("{failed} of {total} contracts failed to verify")
+        }
+        Ok(())
+    }
+}
+
+/// CLI arguments for `forge verify-check-batch`.
+#[derive(Clone, Debug, Parser)]
+pub struct VerifyCheckBatchArgs {
+    /// The path to a file with one verification ID per line, e.g. the output of
+    /// `forge verify-contract --guid-only`.
+    ///
+    /// Only the first word of a line is the ID, the rest is ignored. Empty lines and lines
+    /// starting with `#` are ignored.
+    #[clap(value_hint = ValueHint::FilePath, value_name = "PATH")]
+    pub path: PathBuf,
+
+    #[clap(flatten)]
+    pub retry: RetryArgs,
+
+    #[clap(flatten)]
+    pub etherscan: EtherscanOpts,
+
+    #[clap(flatten)]
+    pub verifier: VerifierArgs,
+}
+
+impl VerifyCheckBatchArgs {
+    /// Checks the verification status of all IDs of the file, one after another, and prints a
+    /// summary of the results.
+    pub async fn run(self) -> Result<()> {
+        let content = foundry_common::fs::read_to_string(&self.path)?;
+        let ids = parse_ids(&content);
+        eyre::ensure!(!ids.is_empty(), "No verification IDs in {}", self.path.display());
+
+        let mut template = VerifyCheckArgs {
+            id: String::new(),
+            retry: self.retry,
+            etherscan: self.etherscan,
+            verifier: self.verifier,
+            quiet: true,
+        };
+        let config = template.load_config_emit_warnings();
+        let chain =
+            template.verifier.etherscan_api_version.resolve(&mut template.etherscan, &config)?;
+        let provider = template
+            .verifier
+            .verifier()
+            .client(&template.etherscan.key, template.etherscan.chain)?;
+
+        let total = ids.len();
+        println!("Checking {total} verifications on {chain}");
+        let mut results = Vec::with_capacity(total);
+        for id in ids {
+            let args = VerifyCheckArgs { id: id.to_string(), ..template.clone() };
+            results.push((id, provider.check(args).await));
+        }
+
+        println!("\nVerification status:");
+        let mut failed = 0;
+        for (id, result) in &results {
+            match result {
+                Ok(()) => println!("  [Pass] {id}"),
+                Err(err) => {
+                    failed += 1;
+                    println!("  [Fail] {id}: {err}");
+                }
+            }
+        }
+
+        if failed > 0 {
+            eyre::bail!("{failed} of {total} contracts are not verified")
+        }
+        Ok(())
+    }
+}
+
+/// Returns the verification IDs of a file, the first word of every line.
+fn parse_ids(content: &str) -> Vec<&str> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_whitespace().next())
+        .collect()
+}
+
+/// Parses the entries of a mapping file into `(<address> <path>:<contractname>, VerifyArgs)`
+/// pairs.
+///
+/// Lines are split like a shell would, so arguments can be quoted. The shared arguments apply to
+/// every entry, but an argument of the entry itself, e.g. `--chain`, overrides them.
+fn parse_entries(content: &str, shared: &[String]) -> Result<Vec<(String, VerifyArgs)>> {
+    let mut entries = Vec::new();
+    for (idx, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue
+        }
+        let invalid = || format!("Invalid entry on line {}: `{line}`", idx + 1);
+
+        let tokens = shlex::split(line)
+            .ok_or_else(|| eyre::eyre!("unbalanced quotes"))
+            .wrap_err_with(invalid)?;
+        let (positional, rest) = tokens.split_at(tokens.len().min(2));
+        let argv = iter::once("forge verify-contract")
+            .chain(positional.iter().map(String::as_str))
+            .chain(shared.iter().map(String::as_str))
+            .chain(rest.iter().map(String::as_str));
+        let matches = VerifyArgs::command()
+            .args_override_self(true)
+            .try_get_matches_from(argv)
+            .wrap_err_with(invalid)?;
+        let args = VerifyArgs::from_arg_matches(&matches).wrap_err_with(invalid)?;
+        entries.push((positional.join(" "), args));
+    }
+
+    eyre::ensure!(!entries.is_empty(), "No contracts to verify");
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use foundry_config::{Chain, NamedChain};
+
+    #[test]
+    fn can_parse_mapping_file() {
+        let content = r#"
+# deployed by script/Deploy.s.sol
+0x0000000000000000000000000000000000000001 src/Counter.sol:Counter
+
+0x0000000000000000000000000000000000000002 src/Token.sol:Token --constructor-args 0x01
+
+# deployed on mainnet
+0x0000000000000000000000000000000000000003 "src/My Token.sol:Token" --chain mainnet
+"#;
+        let entries =
+            parse_entries(content, &["--chain".to_string(), "sepolia".to_string()]).unwrap();
+        assert_eq!(entries.len(), 3);
+        let sepolia = Some(Chain::from_named(NamedChain::Sepolia));
+
+        let (id, args) = &entries[0];
+        assert_eq!(id, "0x0000000000000000000000000000000000000001 src/Counter.sol:Counter");
+        assert_eq!(args.contract.name, "Counter");
+        assert_eq!(args.constructor_args, None);
+        assert_eq!(args.etherscan.chain, sepolia);
+
+        let (_, args) = &entries[1];
+        assert_eq!(args.contract.path.as_deref(), Some("src/Token.sol"));
+        assert_eq!(args.constructor_args.as_deref(), Some("0x01"));
+        assert_eq!(args.etherscan.chain, sepolia);
+
+        // quoted arguments and overrides of the shared arguments
+        let (id, args) = &entries[2];
+        assert_eq!(id, "0x0000000000000000000000000000000000000003 src/My Token.sol:Token");
+        assert_eq!(args.contract.path.as_deref(), Some("src/My Token.sol"));
+        assert_eq!(args.etherscan.chain, Some(Chain::from_named(NamedChain::Mainnet)));
+    }
+
+    #[test]
+    fn can_parse_verification_ids() {
+        let content = "
+# submitted on sepolia
+ezb1abcdefghijklmnopqrstuvwxyz0123456789abcdefghij
+
+ezb2abcdefghijklmnopqrstuvwxyz0123456789abcdefghij src/Token.sol:Token
+";
+        assert_eq!(
+            parse_ids(content),
+            vec![
+                "ezb1abcdefghijklmnopqrstuvwxyz0123456789abcdefghij",
+                "ezb2abcdefghijklmnopqrstuvwxyz0123456789abcdefghij"
+            ]
+        );
+        assert!(parse_ids("# nothing to check\n").is_empty());
+    }
+
+    #[test]
+    fn fails_on_invalid_mapping_file() {
+        let err = parse_entries("src/Counter.sol:Counter", &[]).unwrap_err();
+        assert!(err.to_string().starts_with("Invalid entry on line 1"));
+
+        let err = parse_entries("0x01 \"src/Counter.sol:Counter", &[]).unwrap_err();
+        assert!(err.to_string().starts_with("Invalid entry on line 1"));
+
+        let err = parse_entries("# nothing to do\n", &[]).unwrap_err();
+        assert_eq!(err.to_string(), "No contracts to verify");
+    }
+}
@@ -0,0 +1,83 @@
+//! On-disk cache for the chain IDs of RPC endpoints.
+//!
+//! This avoids an `eth_chainId` round trip for every `forge verify-contract` invocation when
+//! several contracts are verified in a row.
+
+use alloy_primitives::{keccak256, B256};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::Path, time::Duration};
+
+/// The name of the cache file in the project's cache directory.
+pub const CHAIN_ID_CACHE_FILE: &str = "verify-chain-ids.json";
+
+/// How long a cached chain ID is considered valid.
+pub const CHAIN_ID_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Chain IDs keyed by the hash of the RPC URL, so that API keys which are part of the URL are
+/// not written to disk.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainIdCache {
+    entries: BTreeMap<B256, CachedChainId>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct CachedChainId {
+    chain_id: u64,
+    /// Unix timestamp in seconds of when the chain ID was fetched.
+    timestamp: u64,
+}
+
+impl ChainIdCache {
+    /// Reads the cache from the given file, returning an empty cache if it is missing or invalid.
+    pub fn read(path: &Path) -> Self {
+        foundry_common::fs::read_json_file(path).unwrap_or_default()
+    }
+
+    /// Writes the cache to the given file.
+    pub fn write(&self, path: &Path) -> eyre::Result<()> {
+        if let Some(parent) = path.parent() {
+            foundry_common::fs::create_dir_all(parent)?;
+        }
+        foundry_common::fs::write_json_file(path, self)?;
+        Ok(())
+    }
+
+    /// Returns the cached chain ID of the RPC URL, if it was fetched within the
+    /// [CHAIN_ID_CACHE_TTL].
+    pub fn get(&self, rpc_url: &str, now: u64) -> Option<u64> {
+        let entry = self.entries.get(&keccak256(rpc_url))?;
+        (now.saturating_sub(entry.timestamp) < CHAIN_ID_CACHE_TTL.as_secs())
+            .then_some(entry.chain_id)
+    }
+
+    /// Caches the chain ID of the RPC URL.
+    pub fn insert(&mut self, rpc_url: &str, chain_id: u64, now: u64) {
+        self.entries.insert(keccak256(rpc_url), CachedChainId { chain_id, timestamp: now });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_cache_chain_id() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("cache").join(CHAIN_ID_CACHE_FILE);
+        assert_eq!(ChainIdCache::read(&path), ChainIdCache::default());
+
+        let url = "https://eth-mainnet.alchemyapi.io/v2/secret";
+        let mut cache = ChainIdCache::default();
+        cache.insert(url, 1, 100);
+        cache.write(&path).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(!content.contains("secret"));
+
+        let cache = ChainIdCache::read(&path);
+        assert_eq!(cache.get(url, 100), Some(1));
+        assert_eq!(cache.get(url, 100 + CHAIN_ID_CACHE_TTL.as_secs() - 1), Some(1));
+        assert_eq!(cache.get(url, 100 + CHAIN_ID_CACHE_TTL.as_secs()), None);
+        assert_eq!(cache.get("http://localhost:8545", 100), None);
+    }
+}
@@ -0,0 +1,132 @@
+//! Compiler settings read from a metadata file, for `--metadata`.
+//!
+//! This reproduces a historical build, e.g. one made with a vendored solc binary, whose compiler
+//! version isn't reflected by the pragma.
+
+use super::{VerifyArgs, SOLC_DEFAULT_OPTIMIZER_RUNS};
+use eyre::{Context, Result};
+use foundry_compilers::EvmVersion;
+use serde::Deserialize;
+use std::path::Path;
+
+/// The compiler settings of a build.
+///
+/// This is the format of the solc metadata, e.g. the `metadata` of a forge artifact. A plain
+/// settings object, like the `settings` of a standard json input, is accepted as well.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+pub struct CompilerSettingsFile {
+    #[serde(default)]
+    compiler: Option<Compiler>,
+    #[serde(default)]
+    settings: Option<Settings>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+struct Compiler {
+    version: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Settings {
+    #[serde(default)]
+    optimizer: Option<Optimizer>,
+    #[serde(default)]
+    evm_version: Option<EvmVersion>,
+    #[serde(default, rename = "viaIR")]
+    via_ir: Option<bool>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+struct Optimizer {
+    #[serde(default)]
+    enabled: bool,
+    #[serde(default)]
+    runs: Option<usize>,
+}
+
+impl CompilerSettingsFile {
+    /// Reads the compiler settings from the given file.
+    pub fn read(path: &Path) -> Result<Self> {
+        let value: serde_json::Value = foundry_common::fs::read_json_file(path)?;
+        let settings = if value.get("settings").is_some() || value.get("compiler").is_some() {
+            serde_json::from_value(value)
+        } else {
+            serde_json::from_value(value).map(|settings| Self { compiler: None, settings })
+        };
+        settings.wrap_err_with(|| format!("Invalid compiler settings in {}", path.display()))
+    }
+
+    /// Sets the compiler settings of `args` that weren't passed explicitly.
+    pub fn apply(self, args: &mut VerifyArgs) {
+        if let Some(compiler) = self.compiler.filter(|_| args.compiler_path.is_none()) {
+            args.compiler_version.get_or_insert(compiler.version);
+        }
+        let Some(settings) = self.settings else { return };
+
+        if let Some(optimizer) = settings.optimizer {
+            if args.num_of_optimizations.is_none() && !args.no_optimizer {
+                if optimizer.enabled {
+                    args.num_of_optimizations =
+                        Some(optimizer.runs.unwrap_or(SOLC_DEFAULT_OPTIMIZER_RUNS));
+                } else {
+                    args.no_optimizer = true;
+                }
+            }
+        }
+        if args.evm_version.is_none() {
+            args.evm_version = settings.evm_version;
+        }
+        args.via_ir |= settings.via_ir.unwrap_or_default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    fn write(json: &str) -> (tempfile::TempDir, std::path::PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("metadata.json");
+        std::fs::write(&path, json).unwrap();
+        (dir, path)
+    }
+
+    #[test]
+    fn applies_compiler_settings() {
+        let (_dir, path) = write(
+            r#"{
+                "compiler": { "version": "0.8.19+commit.7dd6d404" },
+                "settings": { "optimizer": { "enabled": true, "runs": 1000 }, "evmVersion": "paris" }
+            }"#,
+        );
+        let mut args = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000001",
+            "src/Counter.sol:Counter",
+            "--evm-version",
+            "london",
+        ]);
+        CompilerSettingsFile::read(&path).unwrap().apply(&mut args);
+        assert_eq!(args.compiler_version.as_deref(), Some("0.8.19+commit.7dd6d404"));
+        assert_eq!(args.num_of_optimizations, Some(1000));
+        // explicit flags win over the file
+        assert_eq!(args.evm_version, Some(EvmVersion::London));
+        assert!(!args.via_ir);
+    }
+
+    #[test]
+    fn applies_plain_settings() {
+        let (_dir, path) = write(r#"{ "optimizer": { "enabled": false }, "viaIR": true }"#);
+        let mut args = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000001",
+            "src/Counter.sol:Counter",
+        ]);
+        CompilerSettingsFile::read(&path).unwrap().apply(&mut args);
+        assert!(args.compiler_version.is_none());
+        assert!(args.no_optimizer);
+        assert!(args.via_ir);
+    }
+}
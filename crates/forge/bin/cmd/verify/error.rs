@@ -0,0 +1,98 @@
+use alloy_primitives::Address;
+
+/// The common reasons a verification request fails.
+///
+/// Verification errors are returned as [`eyre::Report`]s, callers can distinguish them with
+/// `err.downcast_ref::<VerifyError>()`. A contract that is already verified is not an error, see
+/// [`VerifyResponse::already_verified`](super::provider::VerifyResponse::already_verified).
+#[derive(Debug, thiserror::Error)]
+pub enum VerifyError {
+    #[error("The deployed bytecode at {address} does not match the local artifact of `{contract}`. Use `--yes` to submit anyway with a warning, or `--force` to skip the safety checks.")]
+    BytecodeMismatch { address: Address, contract: String },
+    #[error("The compiler settings of `{contract}` differ from the settings it was built with. Use `--yes` to submit anyway with a warning, or `--force` to skip the safety checks.")]
+    SettingsMismatch { contract: String },
+    #[error("{0}")]
+    RateLimited(String),
+    #[error("Etherscan could not detect the deployment.")]
+    DeploymentNotFound,
+    #[error("Encountered an error verifying this contract:\nResponse: `{message}`\nDetails: `{details}`")]
+    Rejected { message: String, details: String },
+    #[error(
+        "Contract failed to verify (GUID: `{guid}`):\nResponse: `{message}`\nDetails: `{details}`"
+    )]
+    Failed { guid: String, message: String, details: String },
+    #[error("Verification is still pending, check later with `forge verify-check {guid}`")]
+    Pending { guid: String },
+}
+
+impl VerifyError {
+    /// Returns the process exit code for this error.
+    pub fn exit_code(&self) -> VerifyExitCode {
+        match self {
+            Self::BytecodeMismatch { .. } | Self::SettingsMismatch { .. } => {
+                VerifyExitCode::Mismatch
+            }
+            Self::RateLimited(_) | Self::DeploymentNotFound | Self::Pending { .. } => {
+                VerifyExitCode::Transient
+            }
+            Self::Rejected { .. } | Self::Failed { .. } => VerifyExitCode::Failed,
+        }
+    }
+}
+
+/// The exit codes of the verification commands, so scripts can tell the outcomes apart.
+///
+/// A contract that is already verified exits with [`VerifyExitCode::Verified`] as well.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VerifyExitCode {
+    /// The contract is verified.
+    Verified = 0,
+    /// The verifier rejected the contract.
+    Failed = 1,
+    /// The deployed bytecode or the compiler settings don't match the local contract.
+    Mismatch = 2,
+    /// A transient failure, e.g. a rate limit or a request that is still pending, retrying later
+    /// may succeed.
+    Transient = 3,
+}
+
+impl VerifyExitCode {
+    /// Returns the exit code of the outcome of a verification command.
+    ///
+    /// Errors other than a [`VerifyError`] are reported as [`VerifyExitCode::Failed`].
+    pub fn of<T>(result: &eyre::Result<T>) -> Self {
+        match result {
+            Ok(_) => Self::Verified,
+            Err(err) => {
+                err.downcast_ref::<VerifyError>().map_or(Self::Failed, VerifyError::exit_code)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eyre::WrapErr;
+
+    #[test]
+    fn can_downcast_verify_error() {
+        let err: eyre::Result<()> = Err(VerifyError::BytecodeMismatch {
+            address: Address::ZERO,
+            contract: "Counter".into(),
+        }
+        .into());
+        let err = err.wrap_err("Failed to verify").unwrap_err();
+
+        let verify_err = err.downcast_ref::<VerifyError>().unwrap();
+        assert!(matches!(verify_err, VerifyError::BytecodeMismatch { .. }));
+        assert_eq!(verify_err.exit_code(), VerifyExitCode::Mismatch);
+        assert_eq!(VerifyExitCode::of(&Err::<(), _>(err)) as i32, 2);
+        assert_eq!(VerifyError::DeploymentNotFound.exit_code(), VerifyExitCode::Transient);
+        assert_eq!(
+            VerifyError::Pending { guid: "guid".into() }.exit_code(),
+            VerifyExitCode::Transient
+        );
+        assert_eq!(VerifyExitCode::of(&eyre::Ok(())), VerifyExitCode::Verified);
+    }
+}
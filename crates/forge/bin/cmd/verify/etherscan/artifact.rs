@@ -0,0 +1,270 @@
+//! Verification from a compiled artifact.
+//!
+//! The standard json input is rebuilt from the solc metadata embedded in the artifact, so the
+//! project doesn't need to be compiled again.
+
+use alloy_primitives::{keccak256, B256};
+use eyre::{eyre, Context, Result};
+use foundry_compilers::artifacts::CompactContract;
+use foundry_config::Config;
+use semver::Version;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use std::{collections::BTreeMap, path::Path};
+
+/// The fields of a forge artifact that are needed to rebuild the verification request.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawArtifact {
+    #[serde(default)]
+    raw_metadata: Option<String>,
+}
+
+/// The solc metadata of a contract.
+#[derive(Debug, Deserialize)]
+struct Metadata {
+    compiler: MetadataCompiler,
+    language: String,
+    settings: Map<String, Value>,
+    sources: BTreeMap<String, MetadataSource>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MetadataCompiler {
+    version: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct MetadataSource {
+    keccak256: B256,
+    #[serde(default)]
+    content: Option<String>,
+}
+
+/// A verification request read from a compiled artifact.
+#[derive(Clone, Debug)]
+pub struct ArtifactInput {
+    /// The contract identifier, in the form `<path>:<contractname>`.
+    pub contract_name: String,
+    /// The full version of the compiler the artifact was built with, e.g.
+    /// `v0.8.19+commit.7dd6d404`.
+    pub compiler_version: String,
+    /// The standard json input the artifact was compiled from.
+    pub input: Value,
+    /// The ABI and bytecode of the contract.
+    pub contract: CompactContract,
+}
+
+/// Reads the artifact at `path` and rebuilds the standard json input it was compiled from.
+///
+/// Sources are read relative to `root` and must be unchanged since the artifact was compiled.
+pub fn read_artifact(root: &Path, path: &Path) -> Result<ArtifactInput> {
+    let contract: CompactContract = foundry_common::fs::read_json_file(path)?;
+    let artifact: RawArtifact = foundry_common::fs::read_json_file(path)?;
+    let metadata = artifact.raw_metadata.ok_or_else(|| {
+        eyre!("The artifact {} doesn't contain the contract metadata", path.display())
+    })?;
+    let Metadata { compiler, language, mut settings, sources } = serde_json::from_str(&metadata)
+        .wrap_err_with(|| format!("Invalid metadata in artifact {}", path.display()))?;
+
+    let contract_name = match settings.remove("compilationTarget") {
+        Some(Value::Object(target)) if target.len() == 1 => {
+            let (file, name) = target.into_iter().next().unwrap();
+            format!("{file}:{}", name.as_str().unwrap_or_default())
+        }
+        _ => eyre::bail!("Missing compilation target in the metadata of {}", path.display()),
+    };
+
+    // the metadata lists libraries as `<path>:<name>`, the standard json input groups them by file
+    if let Some(Value::Object(libraries)) = settings.remove("libraries") {
+        let mut grouped = Map::new();
+        for (library, address) in libraries {
+            let (file, name) = library.rsplit_once(':').unwrap_or(("", &library));
+            grouped
+                .entry(file)
+                .or_insert_with(|| Value::Object(Map::new()))
+                .as_object_mut()
+                .unwrap()
+                .insert(name.to_string(), address);
+        }
+        settings.insert("libraries".to_string(), Value::Object(grouped));
+    }
+
+    let mut input_sources = Map::new();
+    for (name, source) in sources {
+        let content = match source.content {
+            Some(content) => content,
+            None => foundry_common::fs::read_to_string(root.join(&name))?,
+        };
+        if keccak256(content.as_bytes()) != source.keccak256 {
+            eyre::bail!(
+                "{name} was modified after the artifact {} was compiled. Rebuild the project or verify the contract by name.",
+                path.display()
+            )
+        }
+        input_sources.insert(name, json!({ "content": content }));
+    }
+
+    Ok(ArtifactInput {
+        contract_name,
+        compiler_version: format!("v{}", compiler.version.trim_start_matches('v')),
+        input: json!({ "language": language, "sources": input_sources, "settings": settings }),
+        contract,
+    })
+}
+
+/// Returns the compiler settings embedded in the metadata of the artifact at `path`, if any.
+pub fn compiler_settings(path: &Path) -> Result<Option<Map<String, Value>>> {
+    let artifact: RawArtifact = foundry_common::fs::read_json_file(path)?;
+    let Some(metadata) = artifact.raw_metadata else { return Ok(None) };
+    let metadata: Metadata = serde_json::from_str(&metadata)
+        .wrap_err_with(|| format!("Invalid metadata in artifact {}", path.display()))?;
+    Ok(Some(metadata.settings))
+}
+
+/// Compares the settings an artifact was built with against the settings of `config`, and
+/// describes every difference that changes the bytecode.
+pub fn settings_divergence(
+    built: &Map<String, Value>,
+    config: &Config,
+    version: &Version,
+) -> Vec<String> {
+    let enabled = |enabled: bool| if enabled { "enabled" } else { "disabled" };
+    let mut divergence = Vec::new();
+
+    let optimizer = built.get("optimizer");
+    let built_optimizer =
+        optimizer.and_then(|o| o.get("enabled")).and_then(Value::as_bool).unwrap_or(false);
+    if built_optimizer != config.optimizer {
+        divergence.push(format!(
+            "the optimizer was {} for the build, but is {} for verification",
+            enabled(built_optimizer),
+            enabled(config.optimizer)
+        ));
+    } else if config.optimizer {
+        let runs = optimizer.and_then(|o| o.get("runs")).and_then(Value::as_u64);
+        if let Some(runs) = runs.filter(|runs| *runs != config.optimizer_runs as u64) {
+            divergence.push(format!(
+                "the build used {runs} optimizer runs, but verification uses {}",
+                config.optimizer_runs
+            ));
+        }
+    }
+
+    let built_evm_version = built.get("evmVersion").and_then(Value::as_str);
+    let evm_version = config.evm_version.normalize_version(version).map(|v| v.to_string());
+    if let (Some(built), Some(evm_version)) = (built_evm_version, evm_version) {
+        if built != evm_version {
+            divergence.push(format!(
+                "the build targeted the `{built}` EVM version, but verification uses `{evm_version}`"
+            ));
+        }
+    }
+
+    let built_hash =
+        built.get("metadata").and_then(|m| m.get("bytecodeHash")).and_then(Value::as_str);
+    let hash = serde_json::to_value(config.bytecode_hash).ok();
+    if let (Some(built), Some(hash)) = (built_hash, hash.as_ref().and_then(Value::as_str)) {
+        if built != hash {
+            divergence.push(format!(
+                "the build used the `{built}` metadata hash, but verification uses `{hash}`"
+            ));
+        }
+    }
+
+    divergence
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COUNTER: &str = "pragma solidity 0.8.19;\ncontract Counter {}\n";
+
+    fn write_artifact(root: &Path, source: &str) -> std::path::PathBuf {
+        let metadata = json!({
+            "compiler": { "version": "0.8.19+commit.7dd6d404" },
+            "language": "Solidity",
+            "settings": {
+                "compilationTarget": { "src/Counter.sol": "Counter" },
+                "evmVersion": "paris",
+                "libraries": { "src/Lib.sol:Lib": "0x0000000000000000000000000000000000000001" },
+                "optimizer": { "enabled": true, "runs": 200 }
+            },
+            "sources": {
+                "src/Counter.sol": { "keccak256": keccak256(source.as_bytes()), "urls": [] }
+            },
+            "version": 1
+        });
+        let artifact = json!({
+            "abi": [],
+            "bytecode": { "object": "0x6080" },
+            "deployedBytecode": { "object": "0x6080" },
+            "rawMetadata": metadata.to_string()
+        });
+        let path = root.join("out/Counter.sol/Counter.json");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, artifact.to_string()).unwrap();
+        path
+    }
+
+    #[test]
+    fn can_read_artifact() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(root.path().join("src")).unwrap();
+        std::fs::write(root.path().join("src/Counter.sol"), COUNTER).unwrap();
+        let path = write_artifact(root.path(), COUNTER);
+
+        let artifact = read_artifact(root.path(), &path).unwrap();
+        assert_eq!(artifact.contract_name, "src/Counter.sol:Counter");
+        assert_eq!(artifact.compiler_version, "v0.8.19+commit.7dd6d404");
+        assert_eq!(artifact.input["sources"]["src/Counter.sol"]["content"], COUNTER);
+        assert_eq!(
+            artifact.input["settings"]["libraries"]["src/Lib.sol"]["Lib"],
+            "0x0000000000000000000000000000000000000001"
+        );
+        assert!(artifact.input["settings"].get("compilationTarget").is_none());
+        assert!(artifact.contract.bin_runtime.is_some());
+    }
+
+    #[test]
+    fn rejects_modified_sources() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(root.path().join("src")).unwrap();
+        std::fs::write(root.path().join("src/Counter.sol"), "contract Counter { uint x; }")
+            .unwrap();
+        let path = write_artifact(root.path(), COUNTER);
+
+        let err = read_artifact(root.path(), &path).unwrap_err();
+        assert!(err.to_string().starts_with("src/Counter.sol was modified after the artifact"));
+    }
+
+    #[test]
+    fn detects_settings_divergence() {
+        let root = tempfile::tempdir().unwrap();
+        let path = write_artifact(root.path(), COUNTER);
+        let built = compiler_settings(&path).unwrap().unwrap();
+        let version = Version::new(0, 8, 19);
+
+        let config = Config {
+            optimizer: true,
+            optimizer_runs: 200,
+            evm_version: foundry_compilers::EvmVersion::Paris,
+            ..Default::default()
+        };
+        assert!(settings_divergence(&built, &config, &version).is_empty());
+
+        let config = Config { optimizer_runs: 1000, ..config };
+        assert_eq!(
+            settings_divergence(&built, &config, &version),
+            vec!["the build used 200 optimizer runs, but verification uses 1000".to_string()]
+        );
+
+        let config = Config { optimizer: false, ..config };
+        assert_eq!(
+            settings_divergence(&built, &config, &version),
+            vec!["the optimizer was enabled for the build, but is disabled for verification"
+                .to_string()]
+        );
+    }
+}
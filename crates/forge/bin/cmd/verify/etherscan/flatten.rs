@@ -1,4 +1,4 @@
-use super::{EtherscanSourceProvider, VerifyArgs};
+use super::{is_yul, normalize_line_endings, EtherscanSourceProvider, VerifyArgs};
 use eyre::{Context, Result};
 use foundry_block_explorers::verify::CodeFormat;
 use foundry_compilers::{
@@ -10,16 +10,25 @@ use std::{collections::BTreeMap, path::Path};
 
 #[derive(Debug)]
 pub struct EtherscanFlattenedSource;
+#[async_trait::async_trait]
 impl EtherscanSourceProvider for EtherscanFlattenedSource {
-    fn source(
+    async fn source(
         &self,
         args: &VerifyArgs,
         project: &Project,
         target: &Path,
         version: &Version,
     ) -> Result<(String, String, CodeFormat)> {
+        eyre::ensure!(
+            !is_yul(target),
+            "Yul contracts can't be flattened, verify them without `--flatten`"
+        );
+
         let metadata = project.solc_config.settings.metadata.as_ref();
-        let bch = metadata.and_then(|m| m.bytecode_hash).unwrap_or_default();
+        let bch = args
+            .compiler_metadata
+            .or_else(|| metadata.and_then(|m| m.bytecode_hash))
+            .unwrap_or_default();
 
         eyre::ensure!(
             bch == BytecodeHash::Ipfs,
@@ -27,11 +36,14 @@ impl EtherscanSourceProvider for EtherscanFlattenedSource {
             bch,
         );
 
-        let source = project.flatten(target).wrap_err("Failed to flatten contract")?;
+        let mut source = project.flatten(target).wrap_err("Failed to flatten contract")?;
+        if args.normalize_line_endings {
+            source = normalize_line_endings(&source);
+        }
 
         if !args.force {
             // solc dry run of flattened code
-            self.check_flattened(source.clone(), version, target).map_err(|err| {
+            self.check_flattened(source.clone(), version, target).await.map_err(|err| {
                 eyre::eyre!(
                     "Failed to compile the flattened code locally: `{}`\
             To skip this solc dry, have a look at the `--force` flag of this command.",
@@ -60,15 +72,17 @@ impl EtherscanFlattenedSource {
     /// If the solc compiler output contains errors, this could either be due to a bug in the
     /// flattening code or could to conflict in the flattened code, for example if there are
     /// multiple interfaces with the same name.
-    fn check_flattened(
+    async fn check_flattened(
         &self,
         content: impl Into<String>,
         version: &Version,
         contract_path: &Path,
     ) -> Result<()> {
         let version = strip_build_meta(version.clone());
-        let solc = Solc::find_svm_installed_version(version.to_string())?
-            .unwrap_or(Solc::blocking_install(&version)?);
+        let solc = match Solc::find_svm_installed_version(version.to_string())? {
+            Some(solc) => solc,
+            None => Solc::install(&version).await?,
+        };
 
         let input = CompilerInput {
             language: "Solidity".to_string(),
@@ -108,3 +122,106 @@ fn strip_build_meta(version: Version) -> Version {
         version
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use foundry_cli::utils::LoadConfig;
+    use foundry_common::fs;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn flattens_shared_imports_once() {
+        let temp = tempdir().unwrap();
+        let root = temp.path();
+        let src = root.join("src");
+        fs::create_dir_all(&src).unwrap();
+
+        let header = "// SPDX-License-Identifier: MIT\npragma solidity ^0.8.0;\n";
+        fs::write(src.join("Base.sol"), format!("{header}contract Base {{}}\n")).unwrap();
+        fs::write(
+            src.join("A.sol"),
+            format!("{header}import \"./Base.sol\";\ncontract A is Base {{}}\n"),
+        )
+        .unwrap();
+        fs::write(
+            src.join("B.sol"),
+            format!("{header}import \"./Base.sol\";\ncontract B is Base {{}}\n"),
+        )
+        .unwrap();
+        fs::write(
+            src.join("Target.sol"),
+            format!(
+                "{header}import \"./A.sol\";\nimport \"./B.sol\";\ncontract Target is A, B {{}}\n"
+            ),
+        )
+        .unwrap();
+
+        let args = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0xd8509bee9c9bf012282ad33aba0d87241baf5064",
+            "src/Target.sol:Target",
+            "--flatten",
+            "--force",
+            "--root",
+            root.to_str().unwrap(),
+        ]);
+        let project = args.load_config().project().unwrap();
+
+        let (source, name, code_format) = EtherscanFlattenedSource
+            .source(&args, &project, &src.join("Target.sol"), &Version::new(0, 8, 19))
+            .await
+            .unwrap();
+
+        assert_eq!(name, "Target");
+        assert_eq!(code_format, CodeFormat::SingleFile);
+        assert_eq!(source.matches("contract Base").count(), 1);
+        assert_eq!(source.matches("SPDX-License-Identifier").count(), 1);
+        assert_eq!(source.matches("pragma solidity").count(), 1);
+    }
+
+    #[tokio::test]
+    async fn flattens_remapped_imports() {
+        let temp = tempdir().unwrap();
+        let root = temp.path();
+        let src = root.join("src");
+        let lib = root.join("lib").join("openzeppelin-contracts").join("contracts");
+        fs::create_dir_all(&src).unwrap();
+        fs::create_dir_all(&lib).unwrap();
+
+        let header = "// SPDX-License-Identifier: MIT\npragma solidity ^0.8.0;\n";
+        fs::write(lib.join("Ownable.sol"), format!("{header}contract Ownable {{}}\n")).unwrap();
+        fs::write(
+            src.join("Counter.sol"),
+            format!(
+                "{header}import \"@openzeppelin/contracts/Ownable.sol\";\ncontract Counter is Ownable {{}}\n"
+            ),
+        )
+        .unwrap();
+        fs::write(
+            root.join("remappings.txt"),
+            "@openzeppelin/contracts/=lib/openzeppelin-contracts/contracts/\n",
+        )
+        .unwrap();
+
+        let args = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0xd8509bee9c9bf012282ad33aba0d87241baf5064",
+            "src/Counter.sol:Counter",
+            "--flatten",
+            "--force",
+            "--root",
+            root.to_str().unwrap(),
+        ]);
+        let project = args.load_config().project().unwrap();
+
+        let (source, _, _) = EtherscanFlattenedSource
+            .source(&args, &project, &src.join("Counter.sol"), &Version::new(0, 8, 19))
+            .await
+            .unwrap();
+
+        assert_eq!(source.matches("contract Ownable").count(), 1);
+        assert!(!source.contains("import"));
+    }
+}
@@ -0,0 +1,74 @@
+//! Etherscan license types.
+//!
+//! Etherscan identifies the license of a verified contract by a numeric `licenseType`, see
+//! <https://etherscan.io/contract-license-types>.
+
+/// The license type etherscan uses for contracts without a license.
+pub const NO_LICENSE: u8 = 1;
+
+/// The SPDX identifiers of the licenses etherscan supports, with their license type.
+///
+/// Identifiers without a version suffix, like `GPL-3.0`, also match the `-only` and `-or-later`
+/// variants.
+const LICENSE_TYPES: &[(&str, u8)] = &[
+    ("UNLICENSED", NO_LICENSE),
+    ("Unlicense", 2),
+    ("MIT", 3),
+    ("GPL-2.0", 4),
+    ("GPL-3.0", 5),
+    ("LGPL-2.1", 6),
+    ("LGPL-3.0", 7),
+    ("BSD-2-Clause", 8),
+    ("BSD-3-Clause", 9),
+    ("MPL-2.0", 10),
+    ("OSL-3.0", 11),
+    ("Apache-2.0", 12),
+    ("AGPL-3.0", 13),
+    ("BUSL-1.1", 14),
+];
+
+/// Returns the etherscan license type of the given SPDX license identifier, if it's supported.
+pub fn license_type(spdx: &str) -> Option<u8> {
+    let spdx = spdx.trim_end_matches("-only").trim_end_matches("-or-later");
+    LICENSE_TYPES.iter().find(|(id, _)| id.eq_ignore_ascii_case(spdx)).map(|(_, ty)| *ty)
+}
+
+/// Returns the SPDX license identifier declared in the `SPDX-License-Identifier` comment of the
+/// source.
+///
+/// Only the first license of an expression like `MIT OR Apache-2.0` is returned.
+pub fn spdx_license(source: &str) -> Option<&str> {
+    source.lines().find_map(|line| {
+        let (_, expr) = line.split_once("SPDX-License-Identifier:")?;
+        expr.trim()
+            .trim_end_matches("*/")
+            .split_whitespace()
+            .next()
+            .map(|id| id.trim_matches(['(', ')']))
+    })
+}
+
+/// Returns the supported SPDX license identifiers, for error messages.
+pub fn supported_licenses() -> String {
+    LICENSE_TYPES.iter().map(|(id, _)| *id).collect::<Vec<_>>().join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_license_type() {
+        let source = "// SPDX-License-Identifier: MIT\npragma solidity ^0.8.0;\n";
+        assert_eq!(spdx_license(source), Some("MIT"));
+        assert_eq!(license_type("MIT"), Some(3));
+
+        let source = "/* SPDX-License-Identifier: (GPL-3.0-or-later OR MIT) */\n";
+        assert_eq!(spdx_license(source), Some("GPL-3.0-or-later"));
+        assert_eq!(license_type("GPL-3.0-or-later"), Some(5));
+
+        assert_eq!(spdx_license("pragma solidity ^0.8.0;"), None);
+        assert_eq!(license_type("UNLICENSED"), Some(NO_LICENSE));
+        assert_eq!(license_type("WTFPL"), None);
+    }
+}
@@ -0,0 +1,126 @@
+//! Decoding of the CBOR encoded metadata solc appends to the bytecode.
+//!
+//! See <https://docs.soliditylang.org/en/latest/metadata.html#encoding-of-the-metadata-hash-in-the-bytecode>.
+
+use super::strip_metadata;
+
+/// Decodes the metadata at the end of `code` into `(key, value)` pairs, e.g. `("solc", "0.8.19")`.
+///
+/// Hashes are returned as hex strings. Returns `None` if the code doesn't end with metadata.
+pub fn decode_metadata(code: &[u8]) -> Option<Vec<(String, String)>> {
+    let start = strip_metadata(code).len();
+    let cbor = code.get(start..code.len().checked_sub(2)?)?;
+    if cbor.is_empty() {
+        return None
+    }
+
+    let mut decoder = Decoder { data: cbor };
+    let (major, len) = decoder.header()?;
+    if major != MAJOR_MAP {
+        return None
+    }
+    let mut entries = Vec::with_capacity(len as usize);
+    for _ in 0..len {
+        let key = match decoder.value()? {
+            Value::Text(key) => key,
+            _ => return None,
+        };
+        let value = match (key.as_str(), decoder.value()?) {
+            // releases encode the version as three bytes, prereleases as a string
+            ("solc", Value::Bytes(&[major, minor, patch])) => format!("{major}.{minor}.{patch}"),
+            (_, Value::Bytes(bytes)) => hex::encode_prefixed(bytes),
+            (_, Value::Text(text)) => text,
+            (_, Value::Bool(value)) => value.to_string(),
+            (_, Value::Uint(value)) => value.to_string(),
+        };
+        entries.push((key, value));
+    }
+    decoder.data.is_empty().then_some(entries)
+}
+
+const MAJOR_UINT: u8 = 0;
+const MAJOR_BYTES: u8 = 2;
+const MAJOR_TEXT: u8 = 3;
+const MAJOR_MAP: u8 = 5;
+const MAJOR_SIMPLE: u8 = 7;
+
+/// The CBOR values solc uses in the metadata.
+enum Value<'a> {
+    Uint(u64),
+    Bytes(&'a [u8]),
+    Text(String),
+    Bool(bool),
+}
+
+/// A minimal CBOR decoder for the subset of CBOR solc emits.
+struct Decoder<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if len > self.data.len() {
+            return None
+        }
+        let (taken, rest) = self.data.split_at(len);
+        self.data = rest;
+        Some(taken)
+    }
+
+    /// Reads the major type and the length or value of the next item.
+    fn header(&mut self) -> Option<(u8, u64)> {
+        let byte = self.take(1)?[0];
+        let (major, info) = (byte >> 5, byte & 0x1f);
+        let arg = match info {
+            0..=23 => info as u64,
+            24..=27 => {
+                let bytes = self.take(1 << (info - 24))?;
+                bytes.iter().fold(0, |acc, b| (acc << 8) | *b as u64)
+            }
+            _ => return None,
+        };
+        Some((major, arg))
+    }
+
+    fn value(&mut self) -> Option<Value<'a>> {
+        let (major, arg) = self.header()?;
+        match major {
+            MAJOR_UINT => Some(Value::Uint(arg)),
+            MAJOR_BYTES => self.take(arg as usize).map(Value::Bytes),
+            MAJOR_TEXT => {
+                let bytes = self.take(arg as usize)?;
+                String::from_utf8(bytes.to_vec()).ok().map(Value::Text)
+            }
+            MAJOR_SIMPLE if arg == 20 || arg == 21 => Some(Value::Bool(arg == 21)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_decode_metadata() {
+        let mut code = vec![0x60, 0x80, 0x60, 0x40];
+        // {"ipfs": <34 bytes>, "solc": 0x000813}
+        code.extend([0xa2, 0x64]);
+        code.extend(b"ipfs");
+        code.extend([0x58, 0x22]);
+        code.extend([0x12, 0x20]);
+        code.extend([0xab; 32]);
+        code.push(0x64);
+        code.extend(b"solc");
+        code.extend([0x43, 0x00, 0x08, 0x13]);
+        code.extend([0x00, 0x33]);
+
+        let metadata = decode_metadata(&code).unwrap();
+        assert_eq!(metadata[0].0, "ipfs");
+        assert_eq!(metadata[0].1, format!("0x1220{}", "ab".repeat(32)));
+        assert_eq!(metadata[1], ("solc".to_string(), "0.8.19".to_string()));
+
+        assert!(decode_metadata(&[0x60, 0x80, 0x60, 0x40]).is_none());
+        assert!(decode_metadata(&[]).is_none());
+    }
+}
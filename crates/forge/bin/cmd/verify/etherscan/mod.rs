@@ -1,6 +1,12 @@
-use super::{provider::VerificationProvider, VerifyArgs, VerifyCheckArgs};
+use super::{
+    provider::{VerificationProvider, VerifyResponse},
+    VerifyArgs, VerifyCheckArgs, VerifyError, SOLC_DEFAULT_OPTIMIZER_RUNS,
+};
 use crate::cmd::retry::RETRY_CHECK_ON_VERIFY;
-use alloy_json_abi::Function;
+use alloy_dyn_abi::JsonAbiExt;
+use alloy_json_abi::{Constructor, JsonAbi};
+use alloy_primitives::Address;
+use ethers_providers::Middleware;
 use eyre::{eyre, Context, Result};
 use foundry_block_explorers::{
     errors::EtherscanError,
@@ -8,37 +14,125 @@ use foundry_block_explorers::{
     verify::{CodeFormat, VerifyContract},
     Client,
 };
-use foundry_cli::utils::{get_cached_entry_by_name, read_constructor_args_file, LoadConfig};
-use foundry_common::{abi::encode_function_args, retry::Retry};
-use foundry_compilers::{artifacts::CompactContract, cache::CacheEntry, Project, Solc};
-use foundry_config::{Chain, Config, SolcReq};
+use foundry_cli::{
+    opts::EtherscanOpts,
+    utils::{get_cached_entry_by_name, read_constructor_args_file, LoadConfig},
+};
+use foundry_common::{
+    abi::{encode_args, encode_args_json},
+    compile::ProjectCompiler,
+    fmt::format_token,
+    retry::{Retry, RetryError},
+    shell,
+    term::SpinnerReporter,
+    types::{ToAlloy, ToEthers},
+};
+use foundry_compilers::{
+    artifacts::{CompactContract, CompactContractBytecode, Offsets, Source},
+    cache::{CacheEntry, SolFilesCache},
+    utils::canonicalized,
+    Graph, Project, Solc,
+};
+use foundry_config::{Chain, Config, NamedChain, SolcReq};
+use foundry_evm::constants::DEFAULT_CREATE2_DEPLOYER;
 use futures::FutureExt;
 use once_cell::sync::Lazy;
+use parking_lot::Mutex;
 use regex::Regex;
-use semver::{BuildMetadata, Version};
+use reqwest::Url;
+use semver::{BuildMetadata, Version, VersionReq};
 use std::{
-    fmt::Debug,
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt::{Debug, Write as _},
+    io::{IsTerminal, Write},
     path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
 };
+use strum::VariantNames;
+use yansi::Paint;
 
+mod artifact;
 mod flatten;
+pub mod license;
+mod metadata;
 mod standard_json;
 
+/// The multichain endpoint of the etherscan v2 API.
+const ETHERSCAN_V2_API_URL: &str = "https://api.etherscan.io/v2/api";
+
+/// The version of the etherscan API to use.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum EtherscanApiVersion {
+    /// The per-chain API hosts, e.g. `api-sepolia.etherscan.io`.
+    #[default]
+    V1,
+    /// The multichain API at `api.etherscan.io/v2`, which accepts a single key for all chains.
+    V2,
+}
+
+impl EtherscanApiVersion {
+    /// Resolves the chain and the API key of the given options for this API version, see
+    /// [EtherscanOpts::resolve].
+    pub fn resolve(self, etherscan: &mut EtherscanOpts, config: &Config) -> Result<Chain> {
+        match self {
+            EtherscanApiVersion::V1 => etherscan.resolve(config),
+            EtherscanApiVersion::V2 => etherscan.resolve_multichain(config),
+        }
+    }
+}
+
+/// The maximum number of linked libraries etherscan accepts for single file submissions.
+const MAX_SINGLE_FILE_LIBRARIES: usize = 10;
+
 pub static RE_BUILD_COMMIT: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"(?P<commit>commit\.[0-9,a-f]{8})").unwrap());
+    Lazy::new(|| Regex::new(r"(?P<commit>commit\.[0-9a-f]{8})").unwrap());
+
+/// Matches the names of the contracts, libraries and interfaces defined in a Solidity source.
+static RE_CONTRACT_DEFINITION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?m)^\s*(?:abstract\s+)?(?:contract|library|interface)\s+([A-Za-z_$][A-Za-z0-9_$]*)",
+    )
+    .unwrap()
+});
+
+/// How long an etherscan API key is skipped after it hit the rate limit.
+const API_KEY_COOLDOWN: Duration = Duration::from_secs(5);
+
+/// The end of the cooldown of every rate limited etherscan API key, shared by all verifications
+/// of the session, see [select_api_key].
+static API_KEY_COOLDOWNS: Lazy<Mutex<HashMap<String, Instant>>> = Lazy::new(Default::default);
+
+/// Memoized solc versions with their build metadata, so the list of solc builds is only fetched
+/// once per session, see [ensure_solc_build_metadata]
+static SOLC_BUILD_METADATA: Lazy<Mutex<HashMap<Version, Version>>> = Lazy::new(Default::default);
 
 #[derive(Clone, Debug, Default)]
 #[non_exhaustive]
 pub struct EtherscanVerificationProvider {
     /// Memoized cached entry of the target contract
     cached_entry: Option<(PathBuf, CacheEntry, CompactContract)>,
+    /// The etherscan clients shared with other verifications, see [VerifyArgs::etherscan_clients]
+    clients: EtherscanClients,
 }
 
+/// The etherscan clients built by a command, by the chain, API URLs and API key they were built
+/// for.
+///
+/// Clones share the same clients, so verifications for the same explorer, e.g. the entries of a
+/// batch, reuse one client instead of creating a new one each.
+#[derive(Clone, Debug, Default)]
+pub struct EtherscanClients(Arc<Mutex<HashMap<ClientId, Client>>>);
+
+/// The chain ID, API URL, explorer URL and API key of an etherscan client.
+type ClientId = (u64, Option<String>, Option<String>, Option<String>);
+
 /// The contract source provider for [EtherscanVerificationProvider]
 ///
 /// Returns source, contract_name and the source [CodeFormat]
+#[async_trait::async_trait]
 trait EtherscanSourceProvider: Send + Sync + Debug {
-    fn source(
+    async fn source(
         &self,
         args: &VerifyArgs,
         project: &Project,
@@ -54,91 +148,9 @@ impl VerificationProvider for EtherscanVerificationProvider {
         Ok(())
     }
 
-    async fn verify(&mut self, args: VerifyArgs) -> Result<()> {
-        let (etherscan, verify_args) = self.prepare_request(&args).await?;
-
-        if !args.skip_is_verified_check &&
-            self.is_contract_verified(&etherscan, &verify_args).await?
-        {
-            println!(
-                "\nContract [{}] {:?} is already verified. Skipping verification.",
-                verify_args.contract_name,
-                verify_args.address.to_checksum(None)
-            );
-
-            return Ok(())
-        }
-
-        trace!(target: "forge::verify", ?verify_args, "submitting verification request");
-
-        let retry: Retry = args.retry.into();
-        let resp = retry
-            .run_async(|| async {
-                println!(
-                    "\nSubmitting verification for [{}] {}.",
-                    verify_args.contract_name, verify_args.address
-                );
-                let resp = etherscan
-                    .submit_contract_verification(&verify_args)
-                    .await
-                    .wrap_err_with(|| {
-                        // valid json
-                        let args = serde_json::to_string(&verify_args).unwrap();
-                        error!(target: "forge::verify", ?args, "Failed to submit verification");
-                        format!("Failed to submit contract verification, payload:\n{args}")
-                    })?;
-
-                trace!(target: "forge::verify", ?resp, "Received verification response");
-
-                if resp.status == "0" {
-                    if resp.result == "Contract source code already verified"
-                        // specific for blockscout response
-                        || resp.result == "Smart-contract already verified."
-                    {
-                        return Ok(None)
-                    }
-
-                    if resp.result.starts_with("Unable to locate ContractCode at") {
-                        warn!("{}", resp.result);
-                        return Err(eyre!("Etherscan could not detect the deployment."))
-                    }
-
-                    warn!("Failed verify submission: {:?}", resp);
-                    eprintln!(
-                    "Encountered an error verifying this contract:\nResponse: `{}`\nDetails: `{}`",
-                    resp.message, resp.result
-                );
-                    std::process::exit(1);
-                }
-
-                Ok(Some(resp))
-            })
-            .await?;
-
-        if let Some(resp) = resp {
-            println!(
-                "Submitted contract for verification:\n\tResponse: `{}`\n\tGUID: `{}`\n\tURL:
-        {}",
-                resp.message,
-                resp.result,
-                etherscan.address_url(args.address)
-            );
-
-            if args.watch {
-                let check_args = VerifyCheckArgs {
-                    id: resp.result,
-                    etherscan: args.etherscan,
-                    retry: RETRY_CHECK_ON_VERIFY,
-                    verifier: args.verifier,
-                };
-                // return check_args.run().await
-                return self.check(check_args).await
-            }
-        } else {
-            println!("Contract source code already verified");
-        }
-
-        Ok(())
+    async fn verify(&mut self, args: VerifyArgs) -> Result<VerifyResponse> {
+        let (config, verify_args) = self.prepare_request(&args).await?;
+        self.submit(&args, &config, verify_args).await
     }
 
     /// Executes the command to check verification status on Etherscan
@@ -147,53 +159,97 @@ impl VerificationProvider for EtherscanVerificationProvider {
         let etherscan = self.client(
             args.etherscan.chain.unwrap_or_default(),
             args.verifier.verifier_url.as_deref(),
+            args.verifier.etherscan_api_version,
             args.etherscan.key.as_deref(),
             &config,
         )?;
+        let tty = std::io::stderr().is_terminal();
+        let quiet = args.quiet;
         let retry: Retry = args.retry.into();
-        retry
-            .run_async(|| {
+        let resp = retry
+            .run_async_until_break(|| {
                 async {
-                    let resp = etherscan
-                        .check_contract_verification_status(args.id.clone())
-                        .await
-                        .wrap_err("Failed to request verification status")?;
+                    let resp =
+                        match etherscan.check_contract_verification_status(args.id.clone()).await {
+                            Ok(resp) => resp,
+                            Err(EtherscanError::RateLimitExceeded) => {
+                                return Err(RetryError::RateLimited(
+                                    VerifyError::RateLimited("Rate limit exceeded".to_string())
+                                        .into(),
+                                ))
+                            }
+                            Err(err) => {
+                                return Err(RetryError::Retry(
+                                    eyre!(err).wrap_err("Failed to request verification status"),
+                                ))
+                            }
+                        };
 
                     trace!(target: "forge::verify", ?resp, "Received verification response");
 
-                    eprintln!(
-                        "Contract verification status:\nResponse: `{}`\nDetails: `{}`",
-                        resp.message, resp.result
-                    );
-
-                    if resp.result == "Pending in queue" {
-                        return Err(eyre!("Verification is still pending...",))
-                    }
-
-                    if resp.result == "Unable to verify" {
-                        return Err(eyre!("Unable to verify.",))
+                    if !quiet {
+                        print_verification_status(tty, &resp.message, &resp.result);
                     }
 
-                    if resp.result == "Already Verified" {
-                        println!("Contract source code already verified");
-                        return Ok(())
+                    if is_rate_limited(&resp.result) {
+                        return Err(RetryError::RateLimited(
+                            VerifyError::RateLimited(resp.result.clone()).into(),
+                        ))
                     }
 
-                    if resp.status == "0" {
-                        println!("Contract failed to verify.");
-                        std::process::exit(1);
+                    let status = VerificationStatus::new(&resp.status, &resp.result);
+                    if status == VerificationStatus::Pending {
+                        return Err(RetryError::Retry(
+                            VerifyError::Pending { guid: args.id.clone() }.into(),
+                        ))
                     }
 
-                    if resp.result == "Pass - Verified" {
-                        println!("Contract successfully verified");
-                    }
-
-                    Ok(())
+                    Ok((resp, status))
                 }
                 .boxed()
             })
-            .await
-            .wrap_err("Checking verification result failed:")
+            .await;
+        if tty && !quiet {
+            // finish the status line
+            eprintln!();
+        }
+
+        let (resp, status) = match resp {
+            Ok(resp) => resp,
+            // still pending once the retries ran out, not a failure
+            Err(err) if matches!(err.downcast_ref(), Some(VerifyError::Pending { .. })) => {
+                return Err(err)
+            }
+            Err(err) => return Err(err.wrap_err("Checking verification result failed:")),
+        };
+
+        match status {
+            VerificationStatus::Verified | VerificationStatus::AlreadyVerified if quiet => {}
+            VerificationStatus::Verified => println!("Contract successfully verified"),
+            VerificationStatus::AlreadyVerified => {
+                println!("Contract source code already verified")
+            }
+            // the status endpoint reports the failure reason in `result`, e.g. `Fail - Unable to
+            // verify. Compiled contract deployment bytecode does NOT match the transaction
+            // deployment bytecode.`
+            VerificationStatus::Failed => {
+                // the status line only reports a short reason, so query the latest status once
+                // more to surface the full details
+                let resp = etherscan
+                    .check_contract_verification_status(args.id.clone())
+                    .await
+                    .unwrap_or(resp);
+                return Err(VerifyError::Failed {
+                    guid: args.id,
+                    message: resp.message,
+                    details: resp.result,
+                }
+                .into())
+            }
+            VerificationStatus::Pending => unreachable!("pending status is retried"),
+        }
+
+        Ok(())
     }
 }
 
@@ -209,33 +265,298 @@ impl EtherscanVerificationProvider {
 
     /// Return the memoized cache entry for the target contract.
     /// Read the artifact from cache on first access.
+    ///
+    /// If the contract path is known, the entry is looked up by path, so that contracts with the
+    /// same name in different files can be told apart. If the file is not part of the cache yet,
+    /// only the file and its imports are compiled, see [compile_target].
     fn cache_entry(
         &mut self,
+        args: &VerifyArgs,
         project: &Project,
-        contract_name: &str,
     ) -> Result<&(PathBuf, CacheEntry, CompactContract)> {
         if let Some(ref entry) = self.cached_entry {
             return Ok(entry)
         }
 
-        let cache = project.read_cache_file()?;
-        let (path, entry) = get_cached_entry_by_name(&cache, contract_name)?;
-        let contract: CompactContract = cache.read_artifact(path.clone(), contract_name)?;
-        Ok(self.cached_entry.insert((path, entry, contract)))
+        let contract = &args.contract;
+
+        let mut cache = project.read_cache_file();
+        if let Some(path) = &contract.path {
+            let path = project.root().join(path);
+            let fresh = !args.recompile &&
+                cache.as_ref().map_or(false, |cache| is_up_to_date(project, cache, &path));
+            if !fresh && project.cached && path.is_file() {
+                if args.recompile {
+                    // without a cache entry the compiler considers the target dirty
+                    if let Ok(cache) = cache.as_mut() {
+                        if cache.files.remove(&path).is_some() {
+                            cache.write(project.cache_path())?;
+                        }
+                    }
+                }
+                let progress = !args.json && std::io::stdout().is_terminal();
+                compile_target(project, &path, progress)?;
+                cache = project.read_cache_file();
+            }
+        }
+        let cache = cache?;
+        let entry = contract.path.as_ref().and_then(|path| {
+            let path = project.root().join(path);
+            cache.files.get(&path).cloned().map(|entry| (path, entry))
+        });
+        let (path, entry) = match entry {
+            Some(entry) => entry,
+            None => get_cached_entry_by_name(&cache, &contract.name)?,
+        };
+        let artifact: CompactContract =
+            cache.read_artifact(path.clone(), &contract.name).wrap_err_with(|| {
+                format!(
+                    "Failed to read the artifact of `{}` from {}. Use `--out` if it was compiled into a different artifacts directory.",
+                    contract.name,
+                    project.paths.artifacts.display()
+                )
+            })?;
+        Ok(self.cached_entry.insert((path, entry, artifact)))
     }
 
     /// Configures the API request to the etherscan API using the given [`VerifyArgs`].
-    async fn prepare_request(&mut self, args: &VerifyArgs) -> Result<(Client, VerifyContract)> {
+    async fn prepare_request(&mut self, args: &VerifyArgs) -> Result<(Config, VerifyContract)> {
+        self.clients = args.etherscan_clients.clone();
         let config = args.try_load_config_emit_warnings()?;
-        let etherscan = self.client(
+        // ensures the client can be created, e.g. that the chain is supported
+        self.client(
             args.etherscan.chain.unwrap_or_default(),
             args.verifier.verifier_url.as_deref(),
+            args.verifier.etherscan_api_version,
             args.etherscan.key.as_deref(),
             &config,
         )?;
-        let verify_args = self.create_verify_request(args, Some(config)).await?;
+        let verify_args = self.create_verify_request(args, Some(config.clone())).await?;
+
+        Ok((config, verify_args))
+    }
+
+    /// Submits a verification request that was written to a file with `--export`.
+    pub async fn verify_export(
+        &mut self,
+        args: &VerifyArgs,
+        verify_args: VerifyContract,
+    ) -> Result<VerifyResponse> {
+        let config = args.try_load_config_emit_warnings()?;
+        self.submit(args, &config, verify_args).await
+    }
+
+    /// Submits the given verification request, unless the contract is already verified.
+    ///
+    /// If several API keys are configured, the submission is retried with the next key right away
+    /// when a key hits the rate limit.
+    async fn submit(
+        &mut self,
+        args: &VerifyArgs,
+        config: &Config,
+        verify_args: VerifyContract,
+    ) -> Result<VerifyResponse> {
+        let chain = args.etherscan.chain.unwrap_or_default();
+        let verifier_url = args.verifier.verifier_url.as_deref();
+        let api_version = args.verifier.etherscan_api_version;
+        let etherscan =
+            self.client(chain, verifier_url, api_version, args.etherscan.key.as_deref(), config)?;
+        let url = etherscan.address_url(verify_args.address);
+
+        if !args.skip_is_verified_check &&
+            self.is_contract_verified(&etherscan, &verify_args).await?
+        {
+            let message = format!(
+                "Contract [{}] {:?} is already verified. Skipping verification.",
+                verify_args.contract_name,
+                verify_args.address.to_checksum(None)
+            );
+            if !args.json {
+                println!("\n{message}\n\tURL: {url}");
+            }
+
+            return Ok(VerifyResponse {
+                guid: None,
+                url: Some(url),
+                message: Some(message),
+                already_verified: true,
+            })
+        }
+
+        if !args.force && !self.deployed_bytecode_matches(args, config).await? {
+            if !args.yes {
+                return Err(VerifyError::BytecodeMismatch {
+                    address: args.address,
+                    contract: args.contract.name.clone(),
+                }
+                .into())
+            }
+            eprintln!(
+                "{}",
+                Paint::yellow(format!(
+                    "Warning: the deployed bytecode at {} does not match the local artifact of `{}`",
+                    args.address, args.contract.name
+                ))
+            );
+        }
 
-        Ok((etherscan, verify_args))
+        trace!(target: "forge::verify", ?verify_args, "submitting verification request");
+
+        let keys = args.etherscan.key.as_deref().map(api_keys).unwrap_or_default();
+        let this = &*self;
+        let retry: Retry = args.retry.into();
+        let resp = retry
+            .run_async_until_break(|| async {
+                if !args.json && shell::verbose_level() > 0 {
+                    let _ = shell::println(format!(
+                        "\nSubmitting verification for [{}] {}.",
+                        verify_args.contract_name, verify_args.address
+                    ));
+                }
+                let key = select_api_key(&keys);
+                let etherscan = this
+                    .client(chain, verifier_url, api_version, key, config)
+                    .map_err(RetryError::Break)?;
+                let resp = match etherscan.submit_contract_verification(&verify_args).await {
+                    Ok(resp) => resp,
+                    Err(EtherscanError::RateLimitExceeded) => {
+                        return Err(rate_limited(
+                            key,
+                            &keys,
+                            VerifyError::RateLimited("Rate limit exceeded".to_string()).into(),
+                        ))
+                    }
+                    Err(err) => {
+                        // valid json
+                        let args = serde_json::to_string(&verify_args).unwrap();
+                        error!(target: "forge::verify", ?args, "Failed to submit verification");
+                        return Err(RetryError::Retry(eyre!(err).wrap_err(format!(
+                            "Failed to submit contract verification, payload:\n{args}"
+                        ))))
+                    }
+                };
+
+                trace!(target: "forge::verify", ?resp, "Received verification response");
+
+                if resp.status == "0" {
+                    if resp.result == "Contract source code already verified"
+                        // specific for blockscout response
+                        || resp.result == "Smart-contract already verified."
+                    {
+                        return Ok(None)
+                    }
+
+                    if is_rate_limited(&resp.result) {
+                        return Err(rate_limited(
+                            key,
+                            &keys,
+                            VerifyError::RateLimited(resp.result.clone()).into(),
+                        ))
+                    }
+
+                    if resp.result.starts_with("Unable to locate ContractCode at") {
+                        warn!("{}", resp.result);
+                        return Err(RetryError::Retry(VerifyError::DeploymentNotFound.into()))
+                    }
+
+                    warn!("Failed verify submission: {:?}", resp);
+                    return Err(RetryError::Break(
+                        VerifyError::Rejected { message: resp.message, details: resp.result }
+                            .into(),
+                    ))
+                }
+
+                Ok(Some(resp))
+            })
+            .await?;
+
+        let Some(resp) = resp else {
+            let message = "Contract source code already verified".to_string();
+            if !args.json {
+                println!("{message}\n\tURL: {url}");
+            }
+            return Ok(VerifyResponse {
+                guid: None,
+                url: Some(url),
+                message: Some(message),
+                already_verified: true,
+            })
+        };
+
+        if !args.json {
+            println!(
+                "Submitted contract for verification:\n\tResponse: `{}`\n\tGUID: `{}`\n\tURL: {url}",
+                resp.message, resp.result,
+            );
+        }
+
+        if args.watch {
+            let check_args = VerifyCheckArgs {
+                id: resp.result.clone(),
+                etherscan: args.etherscan.clone(),
+                retry: RETRY_CHECK_ON_VERIFY,
+                verifier: args.verifier.clone(),
+                quiet: args.quiet,
+            };
+            self.check(check_args).await?;
+            if !args.json {
+                println!("Verified contract URL: {url}");
+            }
+        }
+
+        Ok(VerifyResponse {
+            guid: Some(resp.result),
+            url: Some(url),
+            message: Some(resp.message),
+            already_verified: false,
+        })
+    }
+
+    /// Compares the runtime code deployed at the target address against the cached artifact.
+    ///
+    /// The values of immutable variables and the trailing CBOR metadata are ignored, since they
+    /// are not part of the artifact. The check is skipped if no RPC URL is configured or the
+    /// runtime code of the artifact is unlinked.
+    async fn deployed_bytecode_matches(
+        &mut self,
+        args: &VerifyArgs,
+        config: &Config,
+    ) -> Result<bool> {
+        if config.get_rpc_url().is_none() {
+            return Ok(true)
+        }
+        let project = config.project()?;
+        let artifact_path = match args.artifact_path() {
+            Some(path) => project.root().join(path),
+            None => {
+                let (_, entry, _) = self.cache_entry(args, &project)?;
+                let path = entry
+                    .find_artifact_path(&args.contract.name)
+                    .ok_or_else(|| eyre!("No artifact of `{}` in the cache", args.contract.name))?;
+                project.paths.artifacts.join(path)
+            }
+        };
+        let contract: CompactContractBytecode = foundry_common::fs::read_json_file(&artifact_path)?;
+        let Some(deployed_bytecode) = contract.deployed_bytecode else { return Ok(true) };
+        let Some(local) =
+            deployed_bytecode.bytecode.as_ref().and_then(|code| code.object.as_bytes())
+        else {
+            return Ok(true)
+        };
+
+        let provider = args.provider(config)?;
+        let deployed = provider.get_code(args.address.to_ethers(), None).await?;
+
+        let matches =
+            runtime_code_matches(local, &deployed, &deployed_bytecode.immutable_references);
+        if !matches {
+            warn!(
+                local = local.len(),
+                deployed = deployed.len(),
+                "deployed bytecode does not match the local artifact"
+            );
+        }
+        Ok(matches)
     }
 
     /// Queries the etherscan API to verify if the contract is already verified.
@@ -261,23 +582,48 @@ impl EtherscanVerificationProvider {
         &self,
         chain: Chain,
         verifier_url: Option<&str>,
+        api_version: EtherscanApiVersion,
         etherscan_key: Option<&str>,
         config: &Config,
     ) -> Result<Client> {
         let etherscan_config = config.get_etherscan_config_with_chain(Some(chain))?;
 
-        let etherscan_api_url = verifier_url
-            .or_else(|| etherscan_config.as_ref().map(|c| c.api_url.as_str()))
-            .map(str::to_owned);
+        let etherscan_api_url = match (verifier_url, api_version) {
+            (Some(url), _) => Some(url.to_owned()),
+            // the v2 API serves all chains from a single endpoint, selected by the `chainid`. The
+            // client keeps the query of the URL in its requests, but appends a `/` to the URL,
+            // the empty trailing pair keeps it out of the chain ID.
+            (None, EtherscanApiVersion::V2) => {
+                let mut url = Url::parse(ETHERSCAN_V2_API_URL)?;
+                url.query_pairs_mut()
+                    .append_pair("chainid", &chain.id().to_string())
+                    .append_key_only("");
+                Some(url.to_string())
+            }
+            (None, EtherscanApiVersion::V1) => etherscan_config.as_ref().map(|c| c.api_url.clone()),
+        };
 
         let api_url = etherscan_api_url.as_deref();
-        let base_url = etherscan_config
-            .as_ref()
-            .and_then(|c| c.browser_url.as_deref())
+        // a custom verifier isn't necessarily the chain's etherscan instance, its explorer is
+        // commonly served from the same host as the API
+        let base_url = verifier_url
+            .map(|url| url.trim_end_matches('/').trim_end_matches("/api"))
+            .or_else(|| etherscan_config.as_ref().and_then(|c| c.browser_url.as_deref()))
             .or_else(|| chain.etherscan_urls().map(|(_, url)| url));
 
-        let etherscan_key =
-            etherscan_key.or_else(|| etherscan_config.as_ref().map(|c| c.key.as_str()));
+        let etherscan_key = etherscan_key
+            .or_else(|| etherscan_config.as_ref().map(|c| c.key.as_str()))
+            .and_then(|key| select_api_key(&api_keys(key)));
+
+        let id = (
+            chain.id(),
+            api_url.map(str::to_string),
+            base_url.map(str::to_string),
+            etherscan_key.map(str::to_string),
+        );
+        if let Some(client) = self.clients.0.lock().get(&id) {
+            return Ok(client.clone())
+        }
 
         let mut builder = Client::builder();
 
@@ -286,13 +632,24 @@ impl EtherscanVerificationProvider {
             let api_url = api_url.trim_end_matches('/');
             builder.with_api_url(api_url)?.with_url(base_url.unwrap_or(api_url))?
         } else {
-            builder.chain(chain)?
+            builder.chain(chain).wrap_err_with(|| {
+                let supported = etherscan_supported_chains()
+                    .into_iter()
+                    .map(|chain| format!("{chain} ({})", chain as u64))
+                    .collect::<Vec<_>>();
+                format!(
+                    "Chain {chain} is not supported by etherscan, use `--verifier-url` to set a custom API endpoint.\nSupported chains: {}",
+                    supported.join(", ")
+                )
+            })?
         };
 
-        builder
+        let client = builder
             .with_api_key(etherscan_key.unwrap_or_default())
             .build()
-            .wrap_err("Failed to create etherscan client")
+            .wrap_err("Failed to create etherscan client")?;
+        self.clients.0.lock().insert(id, client.clone());
+        Ok(client)
     }
 
     /// Creates the `VerifyContract` etherscan request in order to verify the contract
@@ -307,21 +664,34 @@ impl EtherscanVerificationProvider {
         let mut config =
             if let Some(config) = config { config } else { args.try_load_config_emit_warnings()? };
 
+        if let Some(path) = args.artifact_path() {
+            return self.create_artifact_verify_request(args, &config, path).await
+        }
+
         config.libraries.extend(args.libraries.clone());
 
         let project = config.project()?;
+        let libraries = linked_libraries(&project)?;
 
         let contract_path = self.contract_path(args, &project)?;
-        let compiler_version = self.compiler_version(args, &config, &project)?;
+        let version = self.compiler_version(args, &config, &project).await?;
+        self.check_compiler_settings(args, &config, &project, &version)?;
         let (source, contract_name, code_format) =
-            self.source_provider(args).source(args, &project, &contract_path, &compiler_version)?;
+            self.source_provider(args).source(args, &project, &contract_path, &version).await?;
 
-        let compiler_version = format!("v{}", ensure_solc_build_metadata(compiler_version).await?);
-        let constructor_args = self.constructor_args(args, &project)?;
+        let compiler_version = format!("v{}", ensure_solc_build_metadata(version.clone()).await?);
+        let constructor_args = self.constructor_args(args, &project, &config).await?;
+        if shell::verbose_level() > 0 && !args.json {
+            if let Some(constructor_args) = constructor_args.as_deref().filter(|c| !c.is_empty()) {
+                self.print_constructor_args(args, &project, constructor_args)?;
+            }
+        }
         let mut verify_args =
             VerifyContract::new(args.address, contract_name, source, compiler_version)
                 .constructor_arguments(constructor_args)
                 .code_format(code_format);
+        let license = license_type(args, &foundry_common::fs::read_to_string(&contract_path)?);
+        verify_args.other.insert("licenseType".to_string(), license.to_string());
 
         if args.via_ir {
             // we explicitly set this __undocumented__ argument to true if provided by the user,
@@ -339,18 +709,111 @@ impl EtherscanVerificationProvider {
             } else {
                 verify_args.not_optimized()
             };
+
+            // the standard json input already carries the evm version in its settings
+            if let Some(evm_version) = config.evm_version.normalize_version(&version) {
+                verify_args = verify_args.evm_version(evm_version.to_string());
+            }
+
+            // etherscan expects linked libraries of single file submissions as numbered
+            // `libraryname`/`libraryaddress` pairs
+            eyre::ensure!(
+                libraries.len() <= MAX_SINGLE_FILE_LIBRARIES,
+                "Etherscan supports at most {MAX_SINGLE_FILE_LIBRARIES} linked libraries for flattened sources, found {}",
+                libraries.len()
+            );
+            for (i, (name, address)) in libraries.into_iter().enumerate() {
+                verify_args.other.insert(format!("libraryname{}", i + 1), name);
+                verify_args.other.insert(format!("libraryaddress{}", i + 1), address.to_string());
+            }
+        }
+
+        if !args.json && shell::verbose_level() > 1 {
+            shell::println(request_summary(&verify_args))?;
+        }
+
+        Ok(verify_args)
+    }
+
+    /// Creates the `VerifyContract` etherscan request from the compiled artifact at `path`,
+    /// without compiling the project.
+    async fn create_artifact_verify_request(
+        &mut self,
+        args: &VerifyArgs,
+        config: &Config,
+        path: &Path,
+    ) -> Result<VerifyContract> {
+        let project = config.project()?;
+        let artifact = artifact::read_artifact(project.root(), &project.root().join(path))?;
+        let constructor_args = self.constructor_args(args, &project, config).await?;
+        let (file, _) = artifact.contract_name.rsplit_once(':').unwrap_or_default();
+        let source = artifact.input["sources"][file]["content"].as_str().unwrap_or_default();
+        let license = license_type(args, source);
+
+        let mut verify_args = VerifyContract::new(
+            args.address,
+            artifact.contract_name,
+            serde_json::to_string(&artifact.input)?,
+            artifact.compiler_version,
+        )
+        .constructor_arguments(constructor_args)
+        .code_format(CodeFormat::StandardJsonInput);
+        verify_args.other.insert("licenseType".to_string(), license.to_string());
+        if args.via_ir {
+            verify_args = verify_args.via_ir(true);
+        }
+
+        if !args.json && shell::verbose_level() > 1 {
+            shell::println(request_summary(&verify_args))?;
         }
 
         Ok(verify_args)
     }
 
+    /// Warns about every compiler setting that differs from the settings the cached artifact was
+    /// built with, since the submitted bytecode wouldn't match the deployment.
+    ///
+    /// Fails on a difference unless `--yes` is set. The check is skipped with `--force`, or if the
+    /// artifact or its metadata is not available.
+    fn check_compiler_settings(
+        &mut self,
+        args: &VerifyArgs,
+        config: &Config,
+        project: &Project,
+        version: &Version,
+    ) -> Result<()> {
+        if args.force {
+            return Ok(())
+        }
+        let Ok((_, entry, _)) = self.cache_entry(args, project) else { return Ok(()) };
+        let artifacts = entry.artifacts.get(&args.contract.name);
+        let Some(path) = artifacts.filter(|a| a.len() == 1).and_then(|a| a.values().next()) else {
+            return Ok(())
+        };
+        let Some(built) = artifact::compiler_settings(&project.paths.artifacts.join(path))? else {
+            return Ok(())
+        };
+
+        let divergence = artifact::settings_divergence(&built, config, version);
+        if divergence.is_empty() {
+            return Ok(())
+        }
+        for difference in &divergence {
+            eprintln!("{}", Paint::yellow(format!("Warning: {difference}")));
+        }
+        if !args.yes {
+            return Err(VerifyError::SettingsMismatch { contract: args.contract.name.clone() }.into())
+        }
+        Ok(())
+    }
+
     /// Get the target contract path. If it wasn't provided, attempt a lookup
     /// in cache. Validate the path indeed exists on disk.
     fn contract_path(&mut self, args: &VerifyArgs, project: &Project) -> Result<PathBuf> {
         let path = match args.contract.path.as_ref() {
             Some(path) => project.root().join(path),
             None => {
-                let (path, _, _) = self.cache_entry(project, &args.contract.name).wrap_err(
+                let (path, _, _) = self.cache_entry(args, project).wrap_err(
                     "If cache is disabled, contract info must be provided in the format <path>:<name>",
                 )?;
                 path.to_owned()
@@ -361,6 +824,9 @@ impl EtherscanVerificationProvider {
         if !path.exists() {
             eyre::bail!("Contract {:?} does not exist.", path);
         }
+        if !is_yul(&path) {
+            ensure_contract_defined(&path, &args.contract.name)?;
+        }
 
         Ok(path)
     }
@@ -370,14 +836,23 @@ impl EtherscanVerificationProvider {
     ///     1. Through CLI arg `--compiler-version`
     ///     2. `solc` defined in foundry.toml
     ///     3. The version contract was last compiled with.
-    fn compiler_version(
+    ///     4. The version the project compiler resolves from the `pragma solidity` of the
+    ///        contract's file and its imports.
+    async fn compiler_version(
         &mut self,
         args: &VerifyArgs,
         config: &Config,
         project: &Project,
     ) -> Result<Version> {
         if let Some(ref version) = args.compiler_version {
-            return Ok(version.trim_start_matches('v').parse()?)
+            return version
+                .trim_start_matches('v')
+                .parse()
+                .wrap_err_with(|| format!("Invalid compiler version `{version}`"))
+        }
+
+        if let Some(ref solc) = args.compiler_path {
+            return solc_long_version(solc)
         }
 
         if let Some(ref solc) = config.solc {
@@ -385,67 +860,780 @@ impl EtherscanVerificationProvider {
                 SolcReq::Version(version) => return Ok(version.to_owned()),
                 SolcReq::Local(solc) => {
                     if solc.is_file() {
-                        return Ok(Solc::new(solc).version()?)
+                        return Solc::new(solc).version().wrap_err_with(|| {
+                            format!("Could not read the version of solc at {}", solc.display())
+                        })
                     }
                 }
             }
         }
 
-        let (_, entry, _) = self.cache_entry(project, &args.contract.name).wrap_err(
-            "If cache is disabled, compiler version must be either provided with `--compiler-version` option or set in foundry.toml"
-        )?;
-        let artifacts = entry.artifacts_versions().collect::<Vec<_>>();
-        if artifacts.len() == 1 {
-            let mut version = artifacts[0].0.to_owned();
-            version.build = match RE_BUILD_COMMIT.captures(version.build.as_str()) {
-                Some(cap) => BuildMetadata::new(cap.name("commit").unwrap().as_str())?,
-                _ => BuildMetadata::EMPTY,
-            };
-            return Ok(version)
-        }
-
-        if artifacts.is_empty() {
-            warn!("No artifacts detected")
-        } else {
-            let versions = artifacts.iter().map(|a| a.0.to_string()).collect::<Vec<_>>();
-            warn!("Ambiguous compiler versions found in cache: {}", versions.join(", "));
-        }
+        match self.cache_entry(args, project) {
+            Ok((_, entry, _)) => {
+                let artifacts = entry.artifacts_versions().collect::<Vec<_>>();
+                if artifacts.len() == 1 {
+                    return strip_build_platform(artifacts[0].0.to_owned())
+                }
+
+                if artifacts.is_empty() {
+                    warn!("No artifacts detected")
+                } else {
+                    let versions = artifacts.iter().map(|a| a.0.to_string()).collect::<Vec<_>>();
+                    warn!("Ambiguous compiler versions found in cache: {}", versions.join(", "));
+                }
+            }
+            Err(err) => {
+                if let Some(version) = pragma_compiler_version(args, project).await? {
+                    return Ok(version)
+                }
+                return Err(err.wrap_err(
+                    "If cache is disabled, compiler version must be either provided with `--compiler-version` option or set in foundry.toml"
+                ))
+            }
+        }
+
+        if let Some(version) = pragma_compiler_version(args, project).await? {
+            return Ok(version)
+        }
 
         eyre::bail!("Compiler version has to be set in `foundry.toml`. If the project was not deployed with foundry, specify the version through `--compiler-version` flag.")
     }
 
+    /// Prints the name, type and decoded value of every constructor argument.
+    ///
+    /// Nothing is printed if the constructor can't be found in the cached artifact.
+    fn print_constructor_args(
+        &mut self,
+        args: &VerifyArgs,
+        project: &Project,
+        encoded: &str,
+    ) -> Result<()> {
+        let Ok((_, _, contract)) = self.cache_entry(args, project) else { return Ok(()) };
+        let Some(constructor) = contract.abi.as_ref().and_then(|abi| abi.constructor()) else {
+            return Ok(())
+        };
+        let values = hex::decode(encoded)
+            .ok()
+            .and_then(|data| constructor.abi_decode_input(&data, false).ok());
+        let Some(values) = values else {
+            warn!("Failed to decode constructor args: 0x{encoded}");
+            return Ok(())
+        };
+
+        let mut output = "Constructor args:".to_string();
+        for (idx, (input, value)) in constructor.inputs.iter().zip(&values).enumerate() {
+            let name = if input.name.is_empty() { format!("arg{idx}") } else { input.name.clone() };
+            write!(output, "\n  {name} ({}): {}", input.selector_type(), format_token(value))?;
+        }
+        shell::println(output)?;
+        Ok(())
+    }
+
     /// Return the optional encoded constructor arguments. If the path to
-    /// constructor arguments was provided, read them and encode. Otherwise,
-    /// return whatever was set in the [VerifyArgs] args.
-    fn constructor_args(&mut self, args: &VerifyArgs, project: &Project) -> Result<Option<String>> {
+    /// constructor arguments was provided, read them and encode. If `--guess-constructor-args` was
+    /// set, try to extract them from the on-chain creation code. If `--no-constructor-args` was
+    /// set, return empty arguments. Otherwise, return whatever was set in the [VerifyArgs] args.
+    async fn constructor_args(
+        &mut self,
+        args: &VerifyArgs,
+        project: &Project,
+        config: &Config,
+    ) -> Result<Option<String>> {
+        if args.no_constructor_args {
+            return Ok(Some(String::new()))
+        }
+
         if let Some(ref constructor_args_path) = args.constructor_args_path {
-            let (_, _, contract) = self.cache_entry(project, &args.contract.name).wrap_err(
-                "Cache must be enabled in order to use the `--constructor-args-path` option",
-            )?;
-            let abi =
-                contract.abi.as_ref().ok_or_else(|| eyre!("Can't find ABI in cached artifact."))?;
-            let constructor = abi
-                .constructor()
-                .ok_or_else(|| eyre!("Can't retrieve constructor info from artifact ABI."))?;
-            #[allow(deprecated)]
-            let func = Function {
-                name: "constructor".to_string(),
-                inputs: constructor.inputs.clone(),
-                outputs: vec![],
-                state_mutability: alloy_json_abi::StateMutability::NonPayable,
+            let abi = self.cached_abi(args, project, "--constructor-args-path")?;
+            let values = read_constructor_args_file(constructor_args_path.to_path_buf())?;
+            return encode_constructor_args(abi, &values).map(Some)
+        }
+
+        if let Some(ref constructor_args_json) = args.constructor_args_json {
+            let constructor = self.cached_constructor(args, project, "--constructor-args-json")?;
+            let values = serde_json::from_str(constructor_args_json)
+                .wrap_err("`--constructor-args-json` must be a JSON array")?;
+            let encoded_args = encode_args_json(&constructor.inputs, &values)
+                .wrap_err("Failed to encode the constructor arguments")?;
+            return Ok(Some(hex::encode(encoded_args)))
+        }
+
+        if args.guess_constructor_args {
+            match self.guess_constructor_args(args, project, config).await {
+                Ok(constructor_args) => {
+                    if !args.json {
+                        println!("Guessed constructor args: 0x{constructor_args}");
+                    }
+                    return Ok(Some(constructor_args))
+                }
+                Err(err) => warn!("Failed to guess constructor args: {err}"),
+            }
+        }
+
+        args.constructor_args
+            .as_deref()
+            .map(|constructor_args| {
+                // etherscan expects the encoded arguments without the `0x` prefix
+                let constructor_args = constructor_args.trim_start_matches("0x");
+                hex::decode(constructor_args).wrap_err_with(|| {
+                    format!("Constructor args must be ABI-encoded hex, got `{constructor_args}`")
+                })?;
+                Ok(constructor_args.to_string())
+            })
+            .transpose()
+    }
+
+    /// Returns the constructor of the target contract from its cached artifact, which the given
+    /// `option` depends on.
+    fn cached_constructor(
+        &mut self,
+        args: &VerifyArgs,
+        project: &Project,
+        option: &str,
+    ) -> Result<Constructor> {
+        self.cached_abi(args, project, option)?
+            .constructor()
+            .cloned()
+            .ok_or_else(|| eyre!("Can't retrieve constructor info from artifact ABI."))
+    }
+
+    /// Returns the ABI of the target contract from its cached artifact, which the given `option`
+    /// depends on.
+    fn cached_abi(
+        &mut self,
+        args: &VerifyArgs,
+        project: &Project,
+        option: &str,
+    ) -> Result<&JsonAbi> {
+        let (_, _, contract) = self.cache_entry(args, project).wrap_err_with(|| {
+            format!("Cache must be enabled in order to use the `{option}` option")
+        })?;
+        contract.abi.as_ref().ok_or_else(|| eyre!("Can't find ABI in cached artifact."))
+    }
+
+    /// Looks up the transaction that created the contract and returns the hex encoded data that
+    /// follows the locally compiled creation code, which are the constructor arguments.
+    async fn guess_constructor_args(
+        &mut self,
+        args: &VerifyArgs,
+        project: &Project,
+        config: &Config,
+    ) -> Result<String> {
+        let creation_code = self.creation_code(args, config, "--guess-constructor-args").await?;
+        let bytecode = self.cached_bytecode(args, project, "--guess-constructor-args")?;
+
+        let constructor_args = extract_constructor_args(&creation_code, bytecode)
+            .ok_or_else(|| eyre!("Local bytecode doesn't match the on-chain creation code"))?;
+        Ok(hex::encode(constructor_args))
+    }
+
+    /// Prints where the on-chain creation code of the contract diverges from the locally compiled
+    /// creation code, for `--show-input`.
+    pub async fn show_creation_code_diff(&mut self, args: &VerifyArgs) -> Result<()> {
+        let config = args.try_load_config_emit_warnings()?;
+        let project = config.project()?;
+        let creation_code = self.creation_code(args, &config, "--show-input").await?;
+        let bytecode = self.cached_bytecode(args, &project, "--show-input")?;
+
+        println!("Local creation code: {} bytes", bytecode.len());
+        println!("On-chain creation code: {} bytes", creation_code.len());
+
+        let Some(offset) = first_difference(bytecode, &creation_code) else {
+            println!(
+                "The on-chain creation code matches the local creation code, followed by {} bytes of constructor arguments",
+                creation_code.len() - bytecode.len()
+            );
+            return Ok(())
+        };
+
+        println!(
+            "The creation code diverges at byte {offset}, in the {}:",
+            divergence_region(bytecode, offset)
+        );
+        let start = offset.saturating_sub(DIFF_CONTEXT);
+        for (name, code) in [("local:   ", bytecode), ("on-chain:", &creation_code[..])] {
+            let end = code.len().min(offset + DIFF_CONTEXT);
+            let (same, different) = if start < end {
+                code[start..end].split_at(offset.min(end) - start)
+            } else {
+                (&[][..], &[][..])
             };
-            let encoded_args = encode_function_args(
-                &func,
-                read_constructor_args_file(constructor_args_path.to_path_buf())?,
-            )?;
-            let encoded_args = hex::encode(encoded_args);
-            return Ok(Some(encoded_args[8..].into()))
+            println!(
+                "  {name} {}{}{}",
+                if start > 0 { "…" } else { "" },
+                hex::encode(same),
+                Paint::red(hex::encode(different))
+            );
+        }
+        Ok(())
+    }
+
+    /// Prints the metadata appended to the local and the on-chain runtime code, for
+    /// `--show-metadata`.
+    ///
+    /// A contract whose code only differs in the metadata hash can only be partially verified.
+    pub async fn show_metadata(&mut self, args: &VerifyArgs) -> Result<()> {
+        let config = args.try_load_config_emit_warnings()?;
+        if config.get_rpc_url().is_none() {
+            eyre::bail!("An RPC URL is required to fetch the on-chain code for `--show-metadata`")
+        }
+        let project = config.project()?;
+        let contract = match args.artifact_path() {
+            Some(path) => {
+                artifact::read_artifact(project.root(), &project.root().join(path))?.contract
+            }
+            None => {
+                let (_, _, contract) = self.cache_entry(args, &project).wrap_err(
+                    "Cache must be enabled in order to use the `--show-metadata` option",
+                )?;
+                contract.clone()
+            }
+        };
+        let local = contract
+            .bin_runtime
+            .as_ref()
+            .and_then(|bin| bin.as_bytes())
+            .ok_or_else(|| eyre!("Can't find the deployed bytecode in the cached artifact."))?;
+
+        let provider = args.provider(&config)?;
+        let deployed = provider.get_code(args.address.to_ethers(), None).await?;
+
+        let local = metadata::decode_metadata(local);
+        let deployed = metadata::decode_metadata(&deployed);
+        for (name, metadata) in [("Local", &local), ("On-chain", &deployed)] {
+            println!("{name} metadata:");
+            match metadata {
+                Some(entries) => {
+                    for (key, value) in entries {
+                        println!("  {key}: {value}");
+                    }
+                }
+                None => println!("  none"),
+            }
         }
 
-        Ok(args.constructor_args.clone())
+        match (&local, &deployed) {
+            (Some(local), Some(deployed)) if local == deployed => {
+                println!("The metadata matches, the contract can be fully verified")
+            }
+            (Some(_), Some(_)) => println!(
+                "{}",
+                Paint::yellow(
+                    "The metadata differs, the contract can only be partially verified. The metadata hash changes with the source files, their paths and the compiler settings."
+                )
+            ),
+            _ => println!("The metadata can't be compared"),
+        }
+        Ok(())
+    }
+
+    /// Looks up the transaction that created the contract and returns its creation code,
+    /// including the constructor arguments.
+    ///
+    /// `option` is the CLI option that requires the creation code.
+    async fn creation_code(
+        &self,
+        args: &VerifyArgs,
+        config: &Config,
+        option: &str,
+    ) -> Result<Vec<u8>> {
+        let etherscan = self.client(
+            args.etherscan.chain.unwrap_or_default(),
+            args.verifier.verifier_url.as_deref(),
+            args.verifier.etherscan_api_version,
+            args.etherscan.key.as_deref(),
+            config,
+        )?;
+        let api_key = args.etherscan.key.as_deref().map(api_keys).unwrap_or_default();
+        let tx_hash = foundry_common::etherscan::contract_creation_tx(
+            &etherscan,
+            select_api_key(&api_key).unwrap_or_default(),
+            args.address,
+        )
+        .await?;
+
+        let provider = args.provider(config)?;
+        let tx = provider
+            .get_transaction(tx_hash.to_ethers())
+            .await?
+            .ok_or_else(|| eyre!("Creation transaction {tx_hash} not found"))?;
+
+        match tx.to {
+            None => Ok(tx.input.to_vec()),
+            // CREATE2 deployments through the default deployer are prefixed with the 32 byte salt
+            Some(to) if to.to_alloy() == DEFAULT_CREATE2_DEPLOYER => {
+                Ok(tx.input.get(32..).unwrap_or_default().to_vec())
+            }
+            Some(_) => {
+                eyre::bail!("`{option}` is not supported for contracts created by other contracts")
+            }
+        }
+    }
+
+    /// Returns the linked creation bytecode of the target contract from its cached artifact,
+    /// which the given `option` depends on.
+    fn cached_bytecode(
+        &mut self,
+        args: &VerifyArgs,
+        project: &Project,
+        option: &str,
+    ) -> Result<&[u8]> {
+        let (_, _, contract) = self.cache_entry(args, project).wrap_err_with(|| {
+            format!("Cache must be enabled in order to use the `{option}` option")
+        })?;
+        contract
+            .bin
+            .as_ref()
+            .and_then(|bin| bin.as_bytes())
+            .map(|bytes| &bytes[..])
+            .ok_or_else(|| eyre!("Can't find linked bytecode in cached artifact."))
     }
 }
 
+/// ABI-encodes the constructor arguments, given as strings, of the constructor in `abi`.
+///
+/// A contract without a constructor only accepts empty arguments.
+fn encode_constructor_args(abi: &JsonAbi, values: &[String]) -> Result<String> {
+    let encoded = match abi.constructor() {
+        Some(constructor) => encode_args(&constructor.inputs, values)?,
+        None if values.is_empty() => Vec::new(),
+        None => eyre::bail!(
+            "Got {} constructor args, but the contract has no constructor",
+            values.len()
+        ),
+    };
+    Ok(hex::encode(encoded))
+}
+
+/// The number of bytes printed before and after the offset where two codes diverge.
+const DIFF_CONTEXT: usize = 16;
+
+/// Returns the offset of the first byte of the local `bytecode` that differs from the on-chain
+/// `creation_code`, or `None` if the creation code starts with the entire bytecode.
+fn first_difference(bytecode: &[u8], creation_code: &[u8]) -> Option<usize> {
+    bytecode
+        .iter()
+        .zip(creation_code)
+        .position(|(local, onchain)| local != onchain)
+        .or_else(|| (creation_code.len() < bytecode.len()).then_some(creation_code.len()))
+}
+
+/// Returns the part of the local `bytecode` the given offset belongs to.
+fn divergence_region(bytecode: &[u8], offset: usize) -> &'static str {
+    if offset >= bytecode.len() {
+        "constructor arguments"
+    } else if offset >= strip_metadata(bytecode).len() {
+        "metadata hash"
+    } else {
+        "code"
+    }
+}
+
+/// Compiles the target source file and its imports, instead of the entire project.
+///
+/// Falls back to compiling the entire project if the target can't be compiled on its own, e.g.
+/// because its imports can't be resolved.
+///
+/// The compiler output isn't printed, if `progress` is set a spinner is shown instead while
+/// compiling.
+fn compile_target(project: &Project, target: &Path, progress: bool) -> Result<()> {
+    let _spinner = progress.then(SpinnerReporter::spawn);
+    let res = ProjectCompiler::new().quiet(true).files([target.to_path_buf()]).compile(project);
+    if let Err(err) = res {
+        warn!(?err, target = %target.display(), "failed to compile target, compiling the entire project");
+        ProjectCompiler::new().quiet(true).compile(project)?;
+    }
+    Ok(())
+}
+
+/// Returns whether the cached artifacts of `target` are up to date, i.e. neither the target nor
+/// any of its imports changed since they were compiled.
+fn is_up_to_date(project: &Project, cache: &SolFilesCache, target: &Path) -> bool {
+    let mut queue = vec![target.to_path_buf()];
+    let mut seen = HashSet::new();
+    while let Some(path) = queue.pop() {
+        if !seen.insert(path.clone()) {
+            continue
+        }
+        let Some(entry) = cache.files.get(&path) else { return false };
+        let unchanged =
+            Source::read(&path).map_or(false, |source| source.content_hash() == entry.content_hash);
+        if !unchanged {
+            return false
+        }
+        queue.extend(entry.imports.iter().map(|import| project.root().join(import)));
+    }
+    true
+}
+
+/// Returns the settings of the verification request, without the source code.
+fn request_summary(verify_args: &VerifyContract) -> String {
+    let mut summary = format!(
+        "Verification request:\n  Contract: {}\n  Compiler version: {}\n  Code format: {:?}\n  Source size: {} bytes",
+        verify_args.contract_name,
+        verify_args.compiler_version,
+        verify_args.code_format,
+        verify_args.source.len()
+    );
+    // standard json submissions carry the optimizer settings in the input
+    let runs = verify_args.runs.clone().or_else(|| {
+        let input: serde_json::Value = serde_json::from_str(&verify_args.source).ok()?;
+        let optimizer = &input["settings"]["optimizer"];
+        optimizer["enabled"].as_bool()?.then(|| optimizer["runs"].to_string())
+    });
+    if let Some(runs) = runs {
+        let default = runs == SOLC_DEFAULT_OPTIMIZER_RUNS.to_string();
+        let _ = write!(
+            summary,
+            "\n  Optimizer runs: {runs}{}",
+            if default { " (solc default)" } else { "" }
+        );
+    }
+    if let Some(evm_version) = &verify_args.evm_version {
+        let _ = write!(summary, "\n  EVM version: {evm_version}");
+    }
+    let constructor_args = verify_args.constructor_arguments.as_deref().unwrap_or_default();
+    let _ = write!(summary, "\n  Constructor args: {} bytes", constructor_args.len() / 2);
+    summary
+}
+
+/// Prints the latest verification status while polling.
+///
+/// On a terminal the status is updated in place, otherwise every status is printed on separate
+/// lines.
+fn print_verification_status(tty: bool, message: &str, result: &str) {
+    if tty {
+        eprint!("\r\x1b[2KContract verification status: {message} - {result}");
+        let _ = std::io::stderr().flush();
+    } else {
+        eprintln!("Contract verification status:\nResponse: `{message}`\nDetails: `{result}`");
+    }
+}
+
+/// The state of a submitted verification request, as reported by the etherscan status endpoint.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum VerificationStatus {
+    /// The request is still being processed, e.g. `Pending in queue`.
+    Pending,
+    /// `Pass - Verified`
+    Verified,
+    /// `Already Verified`
+    AlreadyVerified,
+    /// The verification failed, e.g. `Fail - Unable to verify`.
+    Failed,
+}
+
+impl VerificationStatus {
+    /// Classifies the `status` and `result` of a status response.
+    ///
+    /// Etherscan reports a pending request with status `0`, the same status as a failure, so the
+    /// `result` is checked first. Unrecognized results of successful responses are treated as
+    /// pending, so that polling continues until the verification passes or fails explicitly.
+    fn new(status: &str, result: &str) -> Self {
+        if result.starts_with("Pass - Verified") {
+            Self::Verified
+        } else if result == "Already Verified" {
+            Self::AlreadyVerified
+        } else if result.starts_with("Fail - Unable to verify") {
+            Self::Failed
+        } else if result.eq_ignore_ascii_case("Pending in queue") ||
+            result == "Unable to verify" ||
+            status != "0"
+        {
+            Self::Pending
+        } else {
+            Self::Failed
+        }
+    }
+}
+
+/// Returns `true` if the source file is a Yul file.
+fn is_yul(path: &Path) -> bool {
+    path.extension().map_or(false, |ext| ext.eq_ignore_ascii_case("yul"))
+}
+
+/// Returns the names of the contracts, libraries and interfaces defined in the Solidity source.
+fn defined_contracts(source: &str) -> Vec<&str> {
+    RE_CONTRACT_DEFINITION
+        .captures_iter(source)
+        .filter_map(|cap| cap.get(1))
+        .map(|name| name.as_str())
+        .collect()
+}
+
+/// Fails if the source file at `path` doesn't define the contract `name`, listing the contracts it
+/// defines instead.
+///
+/// Nothing is checked if no contract definitions are found, e.g. because the file is empty.
+fn ensure_contract_defined(path: &Path, name: &str) -> Result<()> {
+    let source = foundry_common::fs::read_to_string(path)?;
+    let contracts = defined_contracts(strip_bom(&source));
+    if !contracts.is_empty() && !contracts.contains(&name) {
+        eyre::bail!(
+            "`{name}` not found in {}, available contracts: {}",
+            path.display(),
+            contracts.join(", ")
+        )
+    }
+    Ok(())
+}
+
+/// Converts CRLF and CR line endings to LF, for `--normalize-line-endings`.
+///
+/// This is for sources checked out with Windows line endings after the contract was compiled
+/// from their LF variant.
+fn normalize_line_endings(content: &str) -> String {
+    content.replace("\r\n", "\n").replace('\r', "\n")
+}
+
+/// Strips the UTF-8 byte order mark some editors silently insert at the start of a file.
+///
+/// This is only for detecting the pragma and the defined contracts, the submitted sources keep
+/// the byte order mark solc compiled them with.
+fn strip_bom(content: &str) -> &str {
+    content.strip_prefix('\u{feff}').unwrap_or(content)
+}
+
+/// Splits a comma separated list of etherscan API keys.
+fn api_keys(key: &str) -> Vec<&str> {
+    key.split(',').map(str::trim).filter(|key| !key.is_empty()).collect()
+}
+
+/// Returns the first of the API keys that isn't cooling down after it hit the rate limit, or the
+/// key whose cooldown ends first.
+fn select_api_key<'a>(keys: &[&'a str]) -> Option<&'a str> {
+    let now = Instant::now();
+    let cooldowns = API_KEY_COOLDOWNS.lock();
+    keys.iter().copied().min_by_key(|key| cooldowns.get(*key).copied().filter(|end| *end > now))
+}
+
+/// Puts the rate limited API key on cooldown.
+///
+/// Returns `true` if another of the keys is available.
+fn cool_down_api_key(key: &str, keys: &[&str]) -> bool {
+    let now = Instant::now();
+    let mut cooldowns = API_KEY_COOLDOWNS.lock();
+    cooldowns.insert(key.to_string(), now + API_KEY_COOLDOWN);
+    keys.iter().any(|key| cooldowns.get(*key).map_or(true, |end| *end <= now))
+}
+
+/// Returns the retry error of a request that was rate limited while using the API `key`.
+///
+/// The request is retried right away if another of the keys is available, otherwise it backs off.
+fn rate_limited(key: Option<&str>, keys: &[&str], err: eyre::Report) -> RetryError {
+    match key {
+        Some(key) if cool_down_api_key(key, keys) => {
+            warn!("etherscan API key hit the rate limit, retrying with the next key");
+            RetryError::Retry(err)
+        }
+        _ => RetryError::RateLimited(err),
+    }
+}
+
+/// Returns `true` if the etherscan response reports an exceeded rate limit, e.g. `Max rate limit
+/// reached, please use API Key for higher rate limit`.
+fn is_rate_limited(result: &str) -> bool {
+    result.to_ascii_lowercase().contains("rate limit")
+}
+
+/// Returns the solc version the project compiler resolves for the contract's source file.
+///
+/// Like the project compiler, this is the newest installed version that satisfies the `pragma
+/// solidity` of the file and of all its imports. If none is installed, the newest matching release
+/// is installed, unless the project is offline. A warning is printed if several installed
+/// versions match, since the contract may have been deployed with another one.
+///
+/// Returns `None` if the contract path is unknown, the file has no version pragma or is a Yul
+/// file, which are built with the configured compiler.
+async fn pragma_compiler_version(args: &VerifyArgs, project: &Project) -> Result<Option<Version>> {
+    let Some(ref path) = args.contract.path else { return Ok(None) };
+    let path = project.root().join(path);
+    if is_yul(&path) {
+        return Ok(None)
+    }
+    let source =
+        Source::read(&path).wrap_err_with(|| format!("Could not read {}", path.display()))?;
+    let Some(req) = pragma_version_req(&source) else { return Ok(None) };
+
+    let mut reqs = vec![req];
+    let graph = Graph::resolve(&project.paths)?;
+    if let Some(&idx) = graph.files().get(&canonicalized(&path)) {
+        reqs.extend(
+            graph
+                .all_imported_nodes(idx)
+                .filter_map(|idx| pragma_version_req(&Source::new(graph.node(idx).content()))),
+        );
+    }
+    let satisfies = |version: &Version| reqs.iter().all(|req| req.matches(version));
+    let pragmas = || reqs.iter().map(|req| format!("`{req}`")).collect::<Vec<_>>().join(", ");
+
+    let mut installed = Solc::installed_versions()
+        .into_iter()
+        .map(Version::from)
+        .filter(|version| satisfies(version))
+        .collect::<Vec<_>>();
+    installed.sort_unstable();
+    if let Some(version) = installed.last() {
+        if installed.len() > 1 {
+            let versions = installed.iter().map(ToString::to_string).collect::<Vec<_>>();
+            eprintln!(
+                "{}",
+                Paint::yellow(format!(
+                    "Warning: the installed solc versions {} all satisfy the version pragmas {} of {}, using {version}. Specify the version through `--compiler-version` flag if the contract was compiled with another one.",
+                    versions.join(", "),
+                    pragmas(),
+                    path.display()
+                ))
+            );
+        }
+        return Ok(Some(version.clone()))
+    }
+
+    let release = Solc::all_versions()
+        .into_iter()
+        .map(Version::from)
+        .filter(|version| satisfies(version))
+        .max()
+        .filter(|_| !project.offline)
+        .ok_or_else(|| {
+            eyre!(
+                "No installed solc version satisfies the version pragmas {} of {}. Specify the version through `--compiler-version` flag.",
+                pragmas(),
+                path.display()
+            )
+        })?;
+    Solc::install(&release).await?;
+    Ok(Some(release))
+}
+
+/// Returns the version requirement of the first `pragma solidity` of the source, if any.
+///
+/// Any whitespace is allowed between `pragma`, `solidity` and the version requirement.
+fn pragma_version_req(source: &Source) -> Option<VersionReq> {
+    Solc::source_version_req(&Source::new(strip_bom(&source.content))).ok()
+}
+
+/// Strips the trailing CBOR encoded metadata from the given bytecode.
+///
+/// The length of the metadata is stored in the last two bytes of the bytecode. If the bytecode
+/// does not end with valid looking metadata, it is returned unchanged.
+fn strip_metadata(code: &[u8]) -> &[u8] {
+    let Some([hi, lo]) = code.len().checked_sub(2).map(|idx| [code[idx], code[idx + 1]]) else {
+        return code
+    };
+    let metadata_len = u16::from_be_bytes([hi, lo]) as usize + 2;
+    code.len().checked_sub(metadata_len).map_or(code, |end| &code[..end])
+}
+
+/// Returns whether the `deployed` runtime code matches the `local` one of the artifact.
+///
+/// The trailing CBOR encoded metadata is skipped, and the bytes at the positions of immutable
+/// variables are ignored, since their values are only set by the constructor.
+fn runtime_code_matches(
+    local: &[u8],
+    deployed: &[u8],
+    immutable_references: &BTreeMap<String, Vec<Offsets>>,
+) -> bool {
+    let (local, deployed) = (strip_metadata(local), strip_metadata(deployed));
+    if local.len() != deployed.len() {
+        return false
+    }
+    let mut masked = deployed.to_vec();
+    for offsets in immutable_references.values().flatten() {
+        let range = offsets.start as usize..(offsets.start + offsets.length) as usize;
+        if let (Some(masked), Some(local)) = (masked.get_mut(range.clone()), local.get(range)) {
+            masked.copy_from_slice(local);
+        }
+    }
+    masked == local
+}
+
+/// Returns the constructor arguments appended to the on-chain `creation_code`, given the local
+/// creation `bytecode` of the contract.
+///
+/// If the creation code doesn't start with the entire bytecode, the trailing CBOR encoded
+/// metadata is skipped while comparing, since its hash differs if the contract was compiled from a
+/// different directory. The length of the metadata must be the same though, so the init code ends
+/// at the same offset.
+fn extract_constructor_args<'a>(creation_code: &'a [u8], bytecode: &[u8]) -> Option<&'a [u8]> {
+    if let Some(args) = creation_code.strip_prefix(bytecode) {
+        return Some(args)
+    }
+
+    let code = strip_metadata(bytecode);
+    if code.len() == bytecode.len() || !creation_code.starts_with(code) {
+        return None
+    }
+    let end = bytecode.len();
+    let metadata_len = creation_code.get(end - 2..end)?;
+    (*metadata_len == bytecode[end - 2..]).then(|| &creation_code[end..])
+}
+
+/// Returns all libraries the project is linked against as `(name, address)` pairs.
+///
+/// Errors if any of the configured library addresses is malformed.
+fn linked_libraries(project: &Project) -> Result<Vec<(String, Address)>> {
+    let mut libraries = Vec::new();
+    for (file, libs) in &project.solc_config.settings.libraries.libs {
+        for (name, address) in libs {
+            let address = address.parse::<Address>().wrap_err_with(|| {
+                format!("Invalid address `{address}` for library `{}:{name}`", file.display())
+            })?;
+            libraries.push((name.clone(), address));
+        }
+    }
+    Ok(libraries)
+}
+
+/// Returns all named chains that have known etherscan API endpoints.
+pub(crate) fn etherscan_supported_chains() -> Vec<NamedChain> {
+    NamedChain::VARIANTS
+        .iter()
+        .filter_map(|name| name.parse::<NamedChain>().ok())
+        .filter(|chain| chain.etherscan_urls().is_some())
+        .collect()
+}
+
+/// Returns the etherscan license type of the contract, either `--license` or the SPDX license
+/// identifier of its source.
+///
+/// Defaults to no license if the source doesn't declare a license etherscan supports.
+fn license_type(args: &VerifyArgs, source: &str) -> u8 {
+    let Some(spdx) = args.license.as_deref().or_else(|| license::spdx_license(source)) else {
+        return license::NO_LICENSE
+    };
+    license::license_type(spdx).unwrap_or_else(|| {
+        warn!("License `{spdx}` is not supported by etherscan, verifying without a license");
+        license::NO_LICENSE
+    })
+}
+
+/// Returns the version of the solc binary at `solc`, including the commit it was built from.
+fn solc_long_version(solc: &Path) -> Result<Version> {
+    let version = Solc::new(solc)
+        .version()
+        .wrap_err_with(|| format!("Could not read the version of solc at {}", solc.display()))?;
+    let version = strip_build_platform(version)?;
+    if version.build.is_empty() {
+        eyre::bail!(
+            "solc at {} doesn't report the commit it was built from, pass the version with `--compiler-version` instead",
+            solc.display()
+        )
+    }
+    Ok(version)
+}
+
+/// Strips everything but the commit from the build metadata of the solc [Version] the contract
+/// was compiled with, since etherscan only knows the platform independent version string, e.g.
+/// `0.8.10+commit.fc410830.Linux.gcc` becomes `0.8.10+commit.fc410830`
+fn strip_build_platform(mut version: Version) -> Result<Version> {
+    version.build = match RE_BUILD_COMMIT.captures(version.build.as_str()) {
+        Some(cap) => BuildMetadata::new(cap.name("commit").unwrap().as_str())?,
+        _ => BuildMetadata::EMPTY,
+    };
+    Ok(version)
+}
+
 /// Given any solc [Version] return a [Version] with build metadata
 ///
 /// # Example
@@ -458,10 +1646,16 @@ impl EtherscanVerificationProvider {
 /// ```
 async fn ensure_solc_build_metadata(version: Version) -> Result<Version> {
     if version.build != BuildMetadata::EMPTY {
-        Ok(version)
-    } else {
-        Ok(lookup_compiler_version(&version).await?)
+        return Ok(version)
     }
+
+    if let Some(full) = SOLC_BUILD_METADATA.lock().get(&version) {
+        return Ok(full.clone())
+    }
+
+    let full = lookup_compiler_version(&version).await?;
+    SOLC_BUILD_METADATA.lock().insert(version, full.clone());
+    Ok(full)
 }
 
 #[cfg(test)]
@@ -504,6 +1698,7 @@ mod tests {
             .client(
                 args.etherscan.chain.unwrap_or_default(),
                 args.verifier.verifier_url.as_deref(),
+                args.verifier.etherscan_api_version,
                 args.etherscan.key.as_deref(),
                 &config,
             )
@@ -531,12 +1726,43 @@ mod tests {
             .client(
                 args.etherscan.chain.unwrap_or_default(),
                 args.verifier.verifier_url.as_deref(),
+                args.verifier.etherscan_api_version,
                 args.etherscan.key.as_deref(),
                 &config,
             )
             .unwrap();
         assert_eq!(client.etherscan_api_url().as_str(), "https://verifier-url.com/?/");
         assert!(format!("{client:?}").contains("dummykey"));
+
+        let args: VerifyArgs = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0xd8509bee9c9bf012282ad33aba0d87241baf5064",
+            "src/Counter.sol:Counter",
+            "--chain",
+            "mumbai",
+            "--etherscan-api-version",
+            "v2",
+            "--root",
+            root.as_os_str().to_str().unwrap(),
+        ]);
+
+        let config = args.load_config();
+
+        let etherscan = EtherscanVerificationProvider::default();
+        let client = etherscan
+            .client(
+                args.etherscan.chain.unwrap_or_default(),
+                args.verifier.verifier_url.as_deref(),
+                args.verifier.etherscan_api_version,
+                args.etherscan.key.as_deref(),
+                &config,
+            )
+            .unwrap();
+        let url = client.etherscan_api_url();
+        assert_eq!(url.path(), "/v2/api");
+        assert_eq!(url.query_pairs().filter(|(key, _)| key == "chainid").count(), 1);
+        assert!(url.query_pairs().any(|(key, value)| key == "chainid" && value == "80001"));
+        assert!(format!("{client:?}").contains("dummykey"));
     }
 
     #[tokio::test(flavor = "multi_thread")]
@@ -608,5 +1834,488 @@ mod tests {
             result.unwrap_err().to_string(),
             "Cache must be enabled in order to use the `--constructor-args-path` option",
         );
+
+        // Malformed compiler version
+        let args = VerifyArgs::parse_from([
+            "foundry-cli",
+            address,
+            &format!("{contract_path}:{contract_name}"),
+            "--compiler-version",
+            "0.8",
+            "--root",
+            root_path,
+        ]);
+
+        let result = etherscan.preflight_check(args).await;
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err().to_string(), "Invalid compiler version `0.8`");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn forwards_optimizer_settings_for_single_file() {
+        let temp = tempdir().unwrap();
+        let root = temp.path();
+        let root_path = root.as_os_str().to_str().unwrap();
+
+        let config = r"
+                [profile.default]
+                optimizer = true
+                optimizer_runs = 200
+            ";
+        fs::write(root.join(Config::FILE_NAME), config).unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/Counter.sol"), "pragma solidity ^0.8.0;\ncontract Counter {}\n")
+            .unwrap();
+
+        let args = [
+            "foundry-cli",
+            "0xd8509bee9c9bf012282ad33aba0d87241baf5064",
+            "src/Counter.sol:Counter",
+            "--flatten",
+            "--force",
+            "--compiler-version",
+            "0.8.19+commit.7dd6d404",
+            "--root",
+            root_path,
+        ];
+
+        // optimizer settings from foundry.toml
+        let mut etherscan = EtherscanVerificationProvider::default();
+        let verify_args =
+            etherscan.create_verify_request(&VerifyArgs::parse_from(args), None).await.unwrap();
+        assert_eq!(verify_args.optimization_used.as_deref(), Some("1"));
+        assert_eq!(verify_args.runs.as_deref(), Some("200"));
+
+        // CLI override wins over foundry.toml
+        let mut etherscan = EtherscanVerificationProvider::default();
+        let verify_args = etherscan
+            .create_verify_request(
+                &VerifyArgs::parse_from(args.into_iter().chain(["--num-of-optimizations", "999"])),
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(verify_args.optimization_used.as_deref(), Some("1"));
+        assert_eq!(verify_args.runs.as_deref(), Some("999"));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn compiler_version_overrides_config() {
+        let temp = tempdir().unwrap();
+        let root = temp.path();
+        let root_path = root.as_os_str().to_str().unwrap();
+
+        fs::write(
+            root.join(Config::FILE_NAME),
+            r#"
+                [profile.default]
+                solc = "0.8.10"
+            "#,
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/Counter.sol"), "pragma solidity ^0.8.0;\ncontract Counter {}\n")
+            .unwrap();
+
+        let args = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0xd8509bee9c9bf012282ad33aba0d87241baf5064",
+            "src/Counter.sol:Counter",
+            "--flatten",
+            "--force",
+            "--compiler-version",
+            "v0.8.19+commit.7dd6d404",
+            "--root",
+            root_path,
+        ]);
+
+        let mut etherscan = EtherscanVerificationProvider::default();
+        let verify_args = etherscan.create_verify_request(&args, None).await.unwrap();
+        assert_eq!(verify_args.compiler_version, "v0.8.19+commit.7dd6d404");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn sets_evm_version_for_single_file() {
+        let temp = tempdir().unwrap();
+        let root = temp.path();
+        let root_path = root.as_os_str().to_str().unwrap();
+
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/Counter.sol"), "pragma solidity ^0.8.0;\ncontract Counter {}\n")
+            .unwrap();
+
+        let args = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0xd8509bee9c9bf012282ad33aba0d87241baf5064",
+            "src/Counter.sol:Counter",
+            "--flatten",
+            "--force",
+            "--compiler-version",
+            "0.8.19+commit.7dd6d404",
+            "--evm-version",
+            "london",
+            "--root",
+            root_path,
+        ]);
+
+        let mut etherscan = EtherscanVerificationProvider::default();
+        let verify_args = etherscan.create_verify_request(&args, None).await.unwrap();
+        assert_eq!(verify_args.evm_version.as_deref(), Some("london"));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn forwards_libraries_for_single_file() {
+        let temp = tempdir().unwrap();
+        let root = temp.path();
+        let root_path = root.as_os_str().to_str().unwrap();
+
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/Counter.sol"), "pragma solidity ^0.8.0;\ncontract Counter {}\n")
+            .unwrap();
+
+        let args = [
+            "foundry-cli",
+            "0xd8509bee9c9bf012282ad33aba0d87241baf5064",
+            "src/Counter.sol:Counter",
+            "--flatten",
+            "--force",
+            "--compiler-version",
+            "0.8.19+commit.7dd6d404",
+            "--root",
+            root_path,
+            "--libraries",
+        ];
+
+        let mut etherscan = EtherscanVerificationProvider::default();
+        let verify_args = etherscan
+            .create_verify_request(
+                &VerifyArgs::parse_from(
+                    args.into_iter()
+                        .chain(["src/Lib.sol:Lib:0x8ce361602b935680e8dec218b820ff5056beb7af"]),
+                ),
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(verify_args.other.get("libraryname1").map(String::as_str), Some("Lib"));
+        assert_eq!(
+            verify_args.other.get("libraryaddress1").map(String::as_str),
+            Some("0x8CE361602B935680E8DeC218b820ff5056BeB7af")
+        );
+
+        let mut etherscan = EtherscanVerificationProvider::default();
+        let err = etherscan
+            .create_verify_request(
+                &VerifyArgs::parse_from(args.into_iter().chain(["src/Lib.sol:Lib:0x1234"])),
+                None,
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Invalid address `0x1234` for library"), "{err}");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn strips_prefix_of_encoded_constructor_args() {
+        let temp = tempdir().unwrap();
+        let root = temp.path();
+        let root_path = root.as_os_str().to_str().unwrap();
+
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/Counter.sol"), "pragma solidity ^0.8.0;\ncontract Counter {}\n")
+            .unwrap();
+
+        let args = [
+            "foundry-cli",
+            "0xd8509bee9c9bf012282ad33aba0d87241baf5064",
+            "src/Counter.sol:Counter",
+            "--flatten",
+            "--force",
+            "--compiler-version",
+            "0.8.19+commit.7dd6d404",
+            "--root",
+            root_path,
+            "--constructor-args",
+        ];
+        let encoded = "0000000000000000000000000000000000000000000000000000000000000001";
+
+        let mut etherscan = EtherscanVerificationProvider::default();
+        let prefixed = format!("0x{encoded}");
+        let verify_args = etherscan
+            .create_verify_request(
+                &VerifyArgs::parse_from(args.into_iter().chain([prefixed.as_str()])),
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(verify_args.constructor_arguments.as_deref(), Some(encoded));
+
+        let mut etherscan = EtherscanVerificationProvider::default();
+        let err = etherscan
+            .create_verify_request(&VerifyArgs::parse_from(args.into_iter().chain(["0xzz"])), None)
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Constructor args must be ABI-encoded hex, got `zz`");
+    }
+
+    #[test]
+    fn lists_etherscan_supported_chains() {
+        let chains = etherscan_supported_chains();
+        assert!(chains.contains(&NamedChain::Mainnet));
+        assert!(chains.contains(&NamedChain::Polygon));
+        assert!(chains.contains(&NamedChain::Arbitrum));
+        assert!(!chains.contains(&NamedChain::Dev));
+    }
+
+    #[test]
+    fn parses_pragma_with_any_whitespace() {
+        for content in [
+            "pragma solidity ^0.8.19;",
+            "pragma  solidity   ^0.8.19;",
+            "pragma\tsolidity\t^0.8.19;",
+            "// SPDX-License-Identifier: MIT\n\tpragma solidity ^0.8.19;\ncontract A {}",
+            "\u{feff}pragma solidity ^0.8.19;",
+        ] {
+            let req = pragma_version_req(&Source::new(content)).unwrap();
+            assert!(req.matches(&Version::new(0, 8, 19)), "{content:?}");
+            assert!(!req.matches(&Version::new(0, 7, 6)), "{content:?}");
+        }
+
+        assert!(pragma_version_req(&Source::new("contract A {}")).is_none());
+    }
+
+    #[test]
+    fn classifies_verification_status() {
+        assert_eq!(VerificationStatus::new("0", "Pending in queue"), VerificationStatus::Pending);
+        assert_eq!(VerificationStatus::new("1", "In progress"), VerificationStatus::Pending);
+        assert_eq!(VerificationStatus::new("1", "Pass - Verified"), VerificationStatus::Verified);
+        assert_eq!(
+            VerificationStatus::new("1", "Already Verified"),
+            VerificationStatus::AlreadyVerified
+        );
+        assert_eq!(
+            VerificationStatus::new(
+                "0",
+                "Fail - Unable to verify. Compiled contract deployment bytecode does NOT match"
+            ),
+            VerificationStatus::Failed
+        );
+        assert_eq!(
+            VerificationStatus::new("0", "Unable to locate ContractCode"),
+            VerificationStatus::Failed
+        );
+    }
+
+    #[test]
+    fn detects_undefined_contract() {
+        let temp = tempdir().unwrap();
+        let path = temp.path().join("Bar.sol");
+        fs::write(
+            &path,
+            "pragma solidity ^0.8.0;\nabstract contract Baz {}\n\nlibrary Qux {}\n// contract Foo\n",
+        )
+        .unwrap();
+
+        assert!(ensure_contract_defined(&path, "Baz").is_ok());
+        let err = ensure_contract_defined(&path, "Foo").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("`Foo` not found in {}, available contracts: Baz, Qux", path.display())
+        );
+    }
+
+    #[test]
+    fn detects_yul_sources() {
+        assert!(is_yul(Path::new("src/Counter.yul")));
+        assert!(is_yul(Path::new("src/Counter.YUL")));
+        assert!(!is_yul(Path::new("src/Counter.sol")));
+        assert!(!is_yul(Path::new("src/yul")));
+    }
+
+    #[test]
+    fn normalizes_line_endings() {
+        assert_eq!(normalize_line_endings("a\r\nb\rc\n"), "a\nb\nc\n");
+        assert_eq!(normalize_line_endings("contract A {}\n"), "contract A {}\n");
+    }
+
+    #[test]
+    fn rotates_rate_limited_api_keys() {
+        assert_eq!(api_keys(" key1, key2,,"), vec!["key1", "key2"]);
+
+        let keys = ["rotate-key1", "rotate-key2"];
+        assert_eq!(select_api_key(&keys), Some("rotate-key1"));
+
+        assert!(cool_down_api_key("rotate-key1", &keys));
+        assert_eq!(select_api_key(&keys), Some("rotate-key2"));
+
+        // all keys are cooling down, the one that was rate limited first is used again
+        assert!(!cool_down_api_key("rotate-key2", &keys));
+        assert_eq!(select_api_key(&keys), Some("rotate-key1"));
+
+        assert!(matches!(
+            rate_limited(Some("rotate-key3"), &["rotate-key3"], eyre!("rate limited")),
+            RetryError::RateLimited(_)
+        ));
+        assert!(select_api_key(&[]).is_none());
+    }
+
+    #[test]
+    fn detects_rate_limited_responses() {
+        assert!(is_rate_limited("Max rate limit reached"));
+        assert!(is_rate_limited(
+            "Max rate limit reached, please use API Key for higher rate limit"
+        ));
+        assert!(!is_rate_limited("Fail - Unable to verify"));
+        assert!(!is_rate_limited("Pending in queue"));
+    }
+
+    #[test]
+    fn strips_bytecode_metadata() {
+        // runtime code followed by `a2646970667358221220<32 bytes>64736f6c6343000813` and the
+        // metadata length
+        let metadata = hex::decode(
+            "a2646970667358221220\
+             0000000000000000000000000000000000000000000000000000000000000000\
+             64736f6c63430008130033",
+        )
+        .unwrap();
+        let mut code = vec![0x60, 0x80, 0x60, 0x40];
+        code.extend_from_slice(&metadata);
+        assert_eq!(strip_metadata(&code), &[0x60, 0x80, 0x60, 0x40]);
+
+        // the metadata length exceeds the code size
+        assert_eq!(strip_metadata(&[0x60, 0xff]), &[0x60, 0xff]);
+        assert_eq!(strip_metadata(&[]), &[] as &[u8]);
+    }
+
+    #[test]
+    fn compares_runtime_code_without_immutables() {
+        // runtime code, followed by 2 bytes of metadata and its length
+        let local = [0x60, 0x80, 0x7f, 0x00, 0x00, 0x60, 0x40, 1, 2, 0x00, 0x02];
+        let deployed = [0x60, 0x80, 0x7f, 0xab, 0xcd, 0x60, 0x40, 3, 4, 0x00, 0x02];
+        let immutables =
+            BTreeMap::from([("42".to_string(), vec![Offsets { start: 3, length: 2 }])]);
+
+        assert!(runtime_code_matches(&local, &deployed, &immutables));
+        assert!(!runtime_code_matches(&local, &deployed, &BTreeMap::new()));
+
+        // code outside of the immutable references differs
+        let mut other = deployed;
+        other[6] = 0x20;
+        assert!(!runtime_code_matches(&local, &other, &immutables));
+        assert!(!runtime_code_matches(&local, &deployed[1..], &immutables));
+    }
+
+    #[test]
+    fn finds_creation_code_divergence() {
+        // runtime code, followed by 8 bytes of metadata and its length
+        let bytecode = [0x60, 0x80, 0x60, 0x40, 1, 2, 3, 4, 5, 6, 7, 8, 0x00, 0x08];
+
+        let mut creation_code = bytecode.to_vec();
+        creation_code.extend([0xaa; 32]);
+        assert_eq!(first_difference(&bytecode, &creation_code), None);
+
+        creation_code[6] = 0xff;
+        assert_eq!(first_difference(&bytecode, &creation_code), Some(6));
+        assert_eq!(divergence_region(&bytecode, 6), "metadata hash");
+
+        creation_code[1] = 0xff;
+        assert_eq!(first_difference(&bytecode, &creation_code), Some(1));
+        assert_eq!(divergence_region(&bytecode, 1), "code");
+
+        assert_eq!(first_difference(&bytecode, &bytecode[..4]), Some(4));
+        assert_eq!(divergence_region(&bytecode, bytecode.len()), "constructor arguments");
+    }
+
+    #[test]
+    fn encodes_constructor_args() {
+        let abi: JsonAbi = serde_json::from_str(
+            r#"[{"type":"constructor","stateMutability":"nonpayable","inputs":[{"name":"owner","type":"address"},{"name":"supply","type":"uint256"}]}]"#,
+        )
+        .unwrap();
+        let values = ["0x000000000000000000000000000000000000dead".to_string(), "1000".to_string()];
+        assert_eq!(
+            encode_constructor_args(&abi, &values).unwrap(),
+            "000000000000000000000000000000000000000000000000000000000000dead00000000000000000000000000000000000000000000000000000000000003e8"
+        );
+
+        let err = encode_constructor_args(&abi, &values[..1]).unwrap_err();
+        assert_eq!(err.to_string(), "expected 2 args, got 1");
+
+        // a constructor without parameters encodes to empty arguments
+        let abi: JsonAbi = serde_json::from_str(
+            r#"[{"type":"constructor","stateMutability":"nonpayable","inputs":[]}]"#,
+        )
+        .unwrap();
+        assert_eq!(encode_constructor_args(&abi, &[]).unwrap(), "");
+
+        let abi = JsonAbi::default();
+        assert_eq!(encode_constructor_args(&abi, &[]).unwrap(), "");
+        let err = encode_constructor_args(&abi, &values).unwrap_err();
+        assert_eq!(err.to_string(), "Got 2 constructor args, but the contract has no constructor");
+    }
+
+    #[test]
+    fn extracts_constructor_args_from_creation_code() {
+        let metadata = |hash: &str| {
+            hex::decode(format!("a2646970667358221220{hash}64736f6c63430008130033")).unwrap()
+        };
+        let init_code = [0x60, 0x80, 0x60, 0x40, 0x52];
+        let args = [0u8; 31].into_iter().chain([42]).collect::<Vec<_>>();
+
+        let mut bytecode = init_code.to_vec();
+        bytecode.extend(metadata(&"11".repeat(32)));
+
+        // identical bytecode
+        let creation_code = [bytecode.as_slice(), &args].concat();
+        assert_eq!(extract_constructor_args(&creation_code, &bytecode), Some(&args[..]));
+
+        // different metadata hash, e.g. compiled from a different directory
+        let creation_code = [&init_code[..], &metadata(&"22".repeat(32)), &args].concat();
+        assert_eq!(extract_constructor_args(&creation_code, &bytecode), Some(&args[..]));
+
+        // without constructor args
+        let creation_code = [&init_code[..], &metadata(&"22".repeat(32))].concat();
+        assert_eq!(extract_constructor_args(&creation_code, &bytecode), Some(&[][..]));
+
+        // different init code
+        let creation_code =
+            [&[0x60, 0x80, 0x60, 0x40, 0x00][..], &metadata(&"11".repeat(32))].concat();
+        assert_eq!(extract_constructor_args(&creation_code, &bytecode), None);
+    }
+
+    #[test]
+    fn strips_build_platform_from_version() {
+        let version = Version::parse("0.8.10+commit.fc410830.Linux.gcc").unwrap();
+        assert_eq!(strip_build_platform(version).unwrap().to_string(), "0.8.10+commit.fc410830");
+
+        let version = Version::parse("0.8.19+commit.7dd6d404").unwrap();
+        assert_eq!(strip_build_platform(version).unwrap().to_string(), "0.8.19+commit.7dd6d404");
+
+        let version = Version::parse("0.8.19").unwrap();
+        assert_eq!(strip_build_platform(version).unwrap().to_string(), "0.8.19");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn reads_long_version_of_solc_binary() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = tempdir().unwrap();
+        let write_solc = |name: &str, version: &str| {
+            let path = temp.path().join(name);
+            fs::write(
+                &path,
+                format!("#!/bin/sh\necho 'solc, the solidity compiler commandline interface'\necho 'Version: {version}'\n"),
+            )
+            .unwrap();
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+            path
+        };
+
+        let solc = write_solc("solc", "0.8.19+commit.7dd6d404.Linux.g++");
+        assert_eq!(solc_long_version(&solc).unwrap().to_string(), "0.8.19+commit.7dd6d404");
+
+        let solc = write_solc("solc-custom", "0.8.19");
+        assert!(solc_long_version(&solc).is_err());
     }
 }
@@ -1,14 +1,26 @@
-use super::{EtherscanSourceProvider, VerifyArgs};
+use super::{is_yul, normalize_line_endings, EtherscanSourceProvider, VerifyArgs};
 use eyre::{Context, Result};
 use foundry_block_explorers::verify::CodeFormat;
-use foundry_compilers::{artifacts::StandardJsonCompilerInput, Project};
+use foundry_compilers::{
+    artifacts::{Source, StandardJsonCompilerInput},
+    Project,
+};
 use semver::Version;
 use std::path::Path;
+use yansi::Paint;
 
+/// The size of a standard json input above which etherscan may reject the submission.
+const LARGE_STANDARD_JSON_SIZE: usize = 8 * 1024 * 1024;
+
+/// Submits the standard json input of the target contract.
+///
+/// The input only contains the sources in the import graph of the target, not every source of the
+/// project, which keeps the request small in large projects.
 #[derive(Debug)]
 pub struct EtherscanStandardJsonSource;
+#[async_trait::async_trait]
 impl EtherscanSourceProvider for EtherscanStandardJsonSource {
-    fn source(
+    async fn source(
         &self,
         args: &VerifyArgs,
         project: &Project,
@@ -31,11 +43,41 @@ impl EtherscanSourceProvider for EtherscanStandardJsonSource {
         // remove all incompatible settings
         input.settings.sanitize(version);
 
+        if let Some(bytecode_hash) = args.compiler_metadata {
+            input.settings.metadata.get_or_insert_with(Default::default).bytecode_hash =
+                Some(bytecode_hash);
+        }
+
+        // etherscan compiles Yul contracts from standard json input as well, the language tells
+        // solc how to parse the sources
+        if is_yul(target) {
+            input.language = "Yul".to_string();
+        }
+
+        if args.normalize_line_endings {
+            for (_, source) in input.sources.iter_mut() {
+                if source.content.contains('\r') {
+                    *source = Source::new(normalize_line_endings(&source.content));
+                }
+            }
+        }
+
         let source =
             serde_json::to_string(&input).wrap_err("Failed to parse standard json input")?;
 
         trace!(target: "forge::verify", standard_json=source, "determined standard json input");
 
+        if source.len() > LARGE_STANDARD_JSON_SIZE {
+            eprintln!(
+                "{}",
+                Paint::yellow(format!(
+                    "Warning: the standard json input of `{}` is {} KiB large and may exceed the request size limit of the verifier",
+                    args.contract.name,
+                    source.len() / 1024
+                ))
+            );
+        }
+
         let name = format!(
             "{}:{}",
             target.strip_prefix(project.root()).unwrap_or(target).display(),
@@ -44,3 +86,223 @@ impl EtherscanSourceProvider for EtherscanStandardJsonSource {
         Ok((source, name, CodeFormat::StandardJsonInput))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use foundry_cli::utils::LoadConfig;
+    use foundry_common::fs;
+    use foundry_config::Config;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn can_create_standard_json_source() {
+        let temp = tempdir().unwrap();
+        let root = temp.path();
+        let src = root.join("src");
+        fs::create_dir_all(&src).unwrap();
+
+        fs::write(src.join("Base.sol"), "pragma solidity ^0.8.0;\ncontract Base {}\n").unwrap();
+        fs::write(src.join("Other.sol"), "pragma solidity ^0.8.0;\ncontract Other {}\n").unwrap();
+        fs::write(
+            src.join("Counter.sol"),
+            "pragma solidity ^0.8.0;\nimport \"./Base.sol\";\ncontract Counter is Base {}\n",
+        )
+        .unwrap();
+
+        let args = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0xd8509bee9c9bf012282ad33aba0d87241baf5064",
+            "src/Counter.sol:Counter",
+            "--root",
+            root.to_str().unwrap(),
+        ]);
+        let project = args.load_config().project().unwrap();
+
+        let (source, name, code_format) = EtherscanStandardJsonSource
+            .source(&args, &project, &src.join("Counter.sol"), &Version::new(0, 8, 19))
+            .await
+            .unwrap();
+
+        assert_eq!(name, "src/Counter.sol:Counter");
+        assert_eq!(code_format, CodeFormat::StandardJsonInput);
+
+        let input: serde_json::Value = serde_json::from_str(&source).unwrap();
+        assert_eq!(input["language"], "Solidity");
+        let sources = input["sources"].as_object().unwrap();
+        assert!(sources.contains_key("src/Counter.sol"));
+        assert!(sources.contains_key("src/Base.sol"));
+        // sources outside of the import graph of the target are not submitted
+        assert!(!sources.contains_key("src/Other.sol"));
+    }
+
+    #[tokio::test]
+    async fn resolves_remapped_imports() {
+        let temp = tempdir().unwrap();
+        let root = temp.path();
+        let src = root.join("src");
+        let lib = root.join("lib").join("openzeppelin-contracts").join("contracts");
+        fs::create_dir_all(&src).unwrap();
+        fs::create_dir_all(&lib).unwrap();
+
+        fs::write(lib.join("Ownable.sol"), "pragma solidity ^0.8.0;\ncontract Ownable {}\n")
+            .unwrap();
+        fs::write(
+            src.join("Counter.sol"),
+            "pragma solidity ^0.8.0;\nimport \"@openzeppelin/contracts/Ownable.sol\";\ncontract Counter is Ownable {}\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join(Config::FILE_NAME),
+            r#"
+                [profile.default]
+                remappings = ["@openzeppelin/contracts/=lib/openzeppelin-contracts/contracts/"]
+            "#,
+        )
+        .unwrap();
+
+        let args = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0xd8509bee9c9bf012282ad33aba0d87241baf5064",
+            "src/Counter.sol:Counter",
+            "--root",
+            root.to_str().unwrap(),
+        ]);
+        let project = args.load_config().project().unwrap();
+
+        let (source, _, _) = EtherscanStandardJsonSource
+            .source(&args, &project, &src.join("Counter.sol"), &Version::new(0, 8, 19))
+            .await
+            .unwrap();
+
+        let input: serde_json::Value = serde_json::from_str(&source).unwrap();
+        let sources = input["sources"].as_object().unwrap();
+        assert!(sources.contains_key("lib/openzeppelin-contracts/contracts/Ownable.sol"));
+        let remappings = input["settings"]["remappings"].as_array().unwrap();
+        assert!(remappings.iter().any(|remapping| remapping ==
+            "@openzeppelin/contracts/=lib/openzeppelin-contracts/contracts/"));
+    }
+
+    #[tokio::test]
+    async fn sets_yul_language() {
+        let temp = tempdir().unwrap();
+        let root = temp.path();
+        let src = root.join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("Counter.yul"), "object \"Counter\" { code { } }\n").unwrap();
+
+        let args = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0xd8509bee9c9bf012282ad33aba0d87241baf5064",
+            "src/Counter.yul:Counter",
+            "--root",
+            root.to_str().unwrap(),
+        ]);
+        let project = args.load_config().project().unwrap();
+
+        let (source, name, code_format) = EtherscanStandardJsonSource
+            .source(&args, &project, &src.join("Counter.yul"), &Version::new(0, 8, 19))
+            .await
+            .unwrap();
+
+        assert_eq!(name, "src/Counter.yul:Counter");
+        assert_eq!(code_format, CodeFormat::StandardJsonInput);
+        let input: serde_json::Value = serde_json::from_str(&source).unwrap();
+        assert_eq!(input["language"], "Yul");
+    }
+
+    #[tokio::test]
+    async fn normalizes_crlf_sources() {
+        let temp = tempdir().unwrap();
+        let root = temp.path();
+        let src = root.join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(
+            src.join("Counter.sol"),
+            "\u{feff}pragma solidity ^0.8.0;\r\ncontract Counter {}\r\n",
+        )
+        .unwrap();
+
+        let mut sources = vec![];
+        for normalize in [true, false] {
+            let mut argv = vec![
+                "foundry-cli",
+                "0xd8509bee9c9bf012282ad33aba0d87241baf5064",
+                "src/Counter.sol:Counter",
+                "--root",
+                root.to_str().unwrap(),
+            ];
+            if normalize {
+                argv.push("--normalize-line-endings");
+            }
+            let args = VerifyArgs::parse_from(argv);
+            let project = args.load_config().project().unwrap();
+            let (source, _, _) = EtherscanStandardJsonSource
+                .source(&args, &project, &src.join("Counter.sol"), &Version::new(0, 8, 19))
+                .await
+                .unwrap();
+            let input: serde_json::Value = serde_json::from_str(&source).unwrap();
+            sources
+                .push(input["sources"]["src/Counter.sol"]["content"].as_str().unwrap().to_string());
+        }
+
+        // the byte order mark is part of the compiled source and is kept
+        assert_eq!(sources[0], "\u{feff}pragma solidity ^0.8.0;\ncontract Counter {}\n");
+        assert_eq!(sources[1], "\u{feff}pragma solidity ^0.8.0;\r\ncontract Counter {}\r\n");
+    }
+
+    #[tokio::test]
+    async fn forwards_bytecode_hash_setting() {
+        let temp = tempdir().unwrap();
+        let root = temp.path();
+        let src = root.join("src");
+        fs::create_dir_all(&src).unwrap();
+
+        fs::write(
+            root.join(Config::FILE_NAME),
+            r#"
+                [profile.default]
+                bytecode_hash = "none"
+            "#,
+        )
+        .unwrap();
+        fs::write(src.join("Counter.sol"), "pragma solidity ^0.8.0;\ncontract Counter {}\n")
+            .unwrap();
+
+        let args = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0xd8509bee9c9bf012282ad33aba0d87241baf5064",
+            "src/Counter.sol:Counter",
+            "--root",
+            root.to_str().unwrap(),
+        ]);
+        let project = args.load_config().project().unwrap();
+
+        let (source, _, _) = EtherscanStandardJsonSource
+            .source(&args, &project, &src.join("Counter.sol"), &Version::new(0, 8, 19))
+            .await
+            .unwrap();
+
+        let input: serde_json::Value = serde_json::from_str(&source).unwrap();
+        assert_eq!(input["settings"]["metadata"]["bytecodeHash"], "none");
+
+        let args = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0xd8509bee9c9bf012282ad33aba0d87241baf5064",
+            "src/Counter.sol:Counter",
+            "--compiler-metadata",
+            "ipfs",
+            "--root",
+            root.to_str().unwrap(),
+        ]);
+        let (source, _, _) = EtherscanStandardJsonSource
+            .source(&args, &project, &src.join("Counter.sol"), &Version::new(0, 8, 19))
+            .await
+            .unwrap();
+
+        // `--compiler-metadata` overrides the config
+        let input: serde_json::Value = serde_json::from_str(&source).unwrap();
+        assert_eq!(input["settings"]["metadata"]["bytecodeHash"], "ipfs");
+    }
+}
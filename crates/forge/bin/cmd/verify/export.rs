@@ -0,0 +1,99 @@
+//! Offline export of etherscan verification requests.
+//!
+//! `forge verify-contract --export <file>` writes the complete verification request to a file,
+//! which can then be submitted from a different machine with `forge verify-contract --from <file>`.
+
+use eyre::{Context, Result};
+use foundry_block_explorers::verify::VerifyContract;
+use foundry_config::Chain;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// The current version of the [VerificationExport] format.
+pub const VERIFICATION_EXPORT_VERSION: u64 = 1;
+
+/// A verification request that was prepared, but not yet submitted.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VerificationExport {
+    /// The version of the format, see [VERIFICATION_EXPORT_VERSION].
+    pub version: u64,
+    /// The ID of the chain the contract is deployed on.
+    pub chain: u64,
+    /// The custom verifier URL the request was prepared for, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verifier_url: Option<String>,
+    /// The etherscan verification request, containing the address, the sources, the compiler
+    /// settings and the constructor arguments.
+    pub request: VerifyContract,
+}
+
+impl VerificationExport {
+    /// Creates a new export of the given request in the current format.
+    pub fn new(chain: Chain, verifier_url: Option<String>, request: VerifyContract) -> Self {
+        Self { version: VERIFICATION_EXPORT_VERSION, chain: chain.id(), verifier_url, request }
+    }
+
+    /// Reads an export from the given file.
+    ///
+    /// Fails if the file was written in a newer format than supported by this version.
+    pub fn read(path: &Path) -> Result<Self> {
+        let value: serde_json::Value = foundry_common::fs::read_json_file(path)?;
+        let version =
+            value.get("version").and_then(serde_json::Value::as_u64).ok_or_else(|| {
+                eyre::eyre!("Missing `version` in verification export {}", path.display())
+            })?;
+        if version > VERIFICATION_EXPORT_VERSION {
+            eyre::bail!(
+                "Unsupported verification export version {version}, the latest supported version is {VERIFICATION_EXPORT_VERSION}. Please update forge."
+            )
+        }
+        serde_json::from_value(value)
+            .wrap_err_with(|| format!("Invalid verification export {}", path.display()))
+    }
+
+    /// Writes the export to the given file.
+    pub fn write(&self, path: &Path) -> Result<()> {
+        foundry_common::fs::write_json_file(path, self)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::Address;
+    use foundry_config::NamedChain;
+
+    #[test]
+    fn can_export_verification_request() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("verify.json");
+
+        let request = VerifyContract::new(
+            Address::with_last_byte(1),
+            "src/Counter.sol:Counter".to_string(),
+            "{}".to_string(),
+            "v0.8.19+commit.7dd6d404".to_string(),
+        )
+        .constructor_arguments(Some("01"));
+        VerificationExport::new(NamedChain::Sepolia.into(), None, request).write(&path).unwrap();
+
+        let export = VerificationExport::read(&path).unwrap();
+        assert_eq!(export.version, VERIFICATION_EXPORT_VERSION);
+        assert_eq!(export.chain, 11155111);
+        assert_eq!(export.verifier_url, None);
+        assert_eq!(export.request.address, Address::with_last_byte(1));
+        assert_eq!(export.request.contract_name, "src/Counter.sol:Counter");
+        assert_eq!(export.request.constructor_arguments.as_deref(), Some("01"));
+    }
+
+    #[test]
+    fn rejects_newer_export_version() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("verify.json");
+        std::fs::write(&path, r#"{"version": 2, "chain": 1}"#).unwrap();
+
+        let err = VerificationExport::read(&path).unwrap_err();
+        assert!(err.to_string().starts_with("Unsupported verification export version 2"));
+    }
+}
@@ -1,19 +1,61 @@
 use super::retry::RetryArgs;
-use alloy_primitives::Address;
-use clap::{Parser, ValueHint};
-use eyre::Result;
-use foundry_cli::{opts::EtherscanOpts, utils::LoadConfig};
-use foundry_compilers::info::ContractInfo;
-use foundry_config::{figment, impl_figment_convert, impl_figment_convert_cast, Config};
+use alloy_primitives::{b256, Address, B256};
+use clap::{ArgAction, Parser, ValueHint};
+use ethers_core::types::NameOrAddress;
+use ethers_providers::Middleware;
+use eyre::{Context, Result};
+use foundry_cli::{
+    opts::{EtherscanOpts, RpcOpts},
+    utils::{self, LoadConfig},
+};
+use foundry_common::{
+    provider::ethers::RetryProvider,
+    shell,
+    types::{ToAlloy, ToEthers},
+};
+use foundry_compilers::{artifacts::BytecodeHash, info::ContractInfo, EvmVersion};
+use foundry_config::{figment, impl_figment_convert, impl_figment_convert_cast, Chain, Config};
 use provider::VerificationProviderType;
 use reqwest::Url;
-use std::path::PathBuf;
+use serde::Serialize;
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The EIP-1967 storage slot of the implementation address,
+/// `bytes32(uint256(keccak256('eip1967.proxy.implementation')) - 1)`.
+const EIP1967_IMPLEMENTATION_SLOT: B256 =
+    b256!("360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc");
+
+/// The number of optimizer runs solc uses if none are configured.
+pub const SOLC_DEFAULT_OPTIMIZER_RUNS: usize = 200;
+
+mod batch;
+pub use batch::{VerifyBatchArgs, VerifyCheckBatchArgs};
+
+mod chain_id_cache;
+use chain_id_cache::{ChainIdCache, CHAIN_ID_CACHE_FILE};
+
+mod compiler_settings;
+use compiler_settings::CompilerSettingsFile;
+
+mod error;
+pub use error::{VerifyError, VerifyExitCode};
 
 mod etherscan;
-use etherscan::EtherscanVerificationProvider;
+use etherscan::{EtherscanApiVersion, EtherscanClients, EtherscanVerificationProvider};
+
+mod export;
+use export::VerificationExport;
+
+mod params;
+pub use params::{verify_contract, VerifyParams};
 
 pub mod provider;
-use provider::VerificationProvider;
+use provider::{VerificationProvider, VerifyResponse};
 
 mod sourcify;
 
@@ -21,29 +63,113 @@ mod sourcify;
 #[derive(Clone, Debug, Parser)]
 pub struct VerifierArgs {
     /// The contract verification provider to use.
-    #[clap(long, help_heading = "Verifier options", default_value = "etherscan", value_enum)]
-    pub verifier: VerificationProviderType,
+    ///
+    /// Can be repeated to verify the contract with several providers, e.g.
+    /// `--verifier etherscan --verifier sourcify`.
+    #[clap(
+        long = "verifier",
+        help_heading = "Verifier options",
+        default_value = "etherscan",
+        value_enum,
+        action = ArgAction::Append
+    )]
+    pub verifiers: Vec<VerificationProviderType>,
 
     /// The verifier URL, if using a custom provider
-    #[clap(long, help_heading = "Verifier options", env = "VERIFIER_URL")]
+    ///
+    /// Required for Blockscout, e.g. `https://eth.blockscout.com/api`.
+    #[clap(
+        long,
+        help_heading = "Verifier options",
+        env = "VERIFIER_URL",
+        required_if_eq("verifiers", "blockscout")
+    )]
     pub verifier_url: Option<String>,
+
+    /// The version of the etherscan API to use.
+    ///
+    /// The v2 API serves all chains from a single endpoint, with a single API key. Ignored if
+    /// `--verifier-url` is set.
+    #[clap(
+        long,
+        help_heading = "Verifier options",
+        env = "ETHERSCAN_API_VERSION",
+        value_enum,
+        default_value = "v1"
+    )]
+    pub etherscan_api_version: EtherscanApiVersion,
 }
 
 impl Default for VerifierArgs {
     fn default() -> Self {
-        VerifierArgs { verifier: VerificationProviderType::Etherscan, verifier_url: None }
+        VerifierArgs {
+            verifiers: vec![VerificationProviderType::Etherscan],
+            verifier_url: None,
+            etherscan_api_version: Default::default(),
+        }
+    }
+}
+
+impl VerifierArgs {
+    /// Returns the first of the verification providers.
+    ///
+    /// Commands that don't support several providers, like `forge verify-check`, only use this
+    /// one.
+    pub fn verifier(&self) -> VerificationProviderType {
+        self.verifiers.first().cloned().unwrap_or_default()
+    }
+
+    /// Returns the verification providers in the order they were passed, without duplicates.
+    pub fn unique_verifiers(&self) -> Vec<VerificationProviderType> {
+        let mut seen = HashSet::new();
+        self.verifiers.iter().filter(|verifier| seen.insert(*verifier)).cloned().collect()
     }
 }
 
 /// CLI arguments for `forge verify`.
 #[derive(Clone, Debug, Parser)]
 pub struct VerifyArgs {
-    /// The address of the contract to verify.
+    /// The address of the contract to verify, or its ENS name.
+    ///
+    /// ENS names are resolved through the RPC endpoint.
+    #[clap(
+        value_name = "ADDRESS",
+        value_parser = parse_address_or_name,
+        required_unless_present_any = ["list_chains", "from"]
+    )]
+    pub address_or_name: Option<NameOrAddress>,
+
+    /// The address of the contract to verify, resolved from `address_or_name` when the command
+    /// runs.
+    #[clap(skip)]
     pub address: Address,
 
+    /// The provider of the RPC endpoint, connected once when the command runs and shared by
+    /// everything that queries the chain.
+    #[clap(skip)]
+    pub provider: Option<Arc<RetryProvider>>,
+
+    /// The etherscan clients, built once per explorer and API key and shared with other
+    /// verifications of the command, e.g. the other entries of `forge verify-batch`.
+    #[clap(skip)]
+    pub etherscan_clients: EtherscanClients,
+
     /// The contract identifier in the form `<path>:<contractname>`.
+    ///
+    /// This can also be the path to the compiled artifact of the contract, e.g.
+    /// `out/Counter.sol/Counter.json`, to verify it without compiling the project.
+    #[clap(
+        value_parser = utils::parse_contract_info,
+        required_unless_present_any = ["list_chains", "from"],
+        default_value = "_",
+        hide_default_value = true
+    )]
     pub contract: ContractInfo,
 
+    /// Print all chains supported by etherscan verification, with the env var of their API key.
+    #[clap(long, exclusive = true)]
+    pub list_chains: bool,
+
     /// The ABI-encoded constructor arguments.
     #[clap(
         long,
@@ -57,22 +183,133 @@ pub struct VerifyArgs {
     #[clap(long, value_hint = ValueHint::FilePath, value_name = "PATH")]
     pub constructor_args_path: Option<PathBuf>,
 
-    /// The `solc` version to use to build the smart contract.
+    /// The constructor arguments as a JSON array matching the constructor's parameters.
+    ///
+    /// Arrays and tuples are passed as nested JSON arrays, e.g. `'[[1, 2], ["0x..", true]]'` for
+    /// `constructor(uint256[], (address, bool))`. Large numbers should be passed as strings.
+    #[clap(
+        long,
+        conflicts_with_all = ["constructor_args", "constructor_args_path"],
+        value_name = "JSON"
+    )]
+    pub constructor_args_json: Option<String>,
+
+    /// Explicitly submit empty constructor arguments.
+    ///
+    /// Use this for contracts with a constructor without parameters, to make sure no arguments
+    /// are sent regardless of how the contract was deployed.
+    #[clap(
+        long,
+        conflicts_with_all = [
+            "constructor_args",
+            "constructor_args_path",
+            "constructor_args_json",
+            "guess_constructor_args",
+        ]
+    )]
+    pub no_constructor_args: bool,
+
+    /// Try to extract the constructor arguments from the contract's on-chain creation code.
+    ///
+    /// Falls back to `--constructor-args` if the arguments could not be extracted.
+    #[clap(long, conflicts_with = "constructor_args_path")]
+    pub guess_constructor_args: bool,
+
+    /// The `solc` version to use to build the smart contract, e.g. `v0.8.19+commit.7dd6d404`.
+    ///
+    /// Overrides the version detected from the config, the cache or the pragma of the contract.
     #[clap(long, value_name = "VERSION")]
     pub compiler_version: Option<String>,
 
+    /// The path to the `solc` binary the contract was built with, e.g. a vendored solc.
+    ///
+    /// The binary is used to build the contract, and the compiler version is read from its
+    /// `--version` output.
+    #[clap(
+        long,
+        conflicts_with = "compiler_version",
+        value_hint = ValueHint::FilePath,
+        value_name = "PATH"
+    )]
+    pub compiler_path: Option<PathBuf>,
+
+    /// The EVM version to use.
+    ///
+    /// Overrides the version specified in the config.
+    #[clap(long)]
+    pub evm_version: Option<EvmVersion>,
+
     /// The number of optimization runs used to build the smart contract.
-    #[clap(long, visible_alias = "optimizer-runs", value_name = "NUM")]
+    ///
+    /// Implies that the optimizer was enabled. Overrides the optimizer settings of the config.
+    /// Pass `auto` for solc's default of 200 runs.
+    #[clap(
+        long,
+        visible_alias = "optimizer-runs",
+        value_name = "NUM",
+        value_parser = parse_optimizer_runs
+    )]
     pub num_of_optimizations: Option<usize>,
 
+    /// Verify the contract as built without the optimizer.
+    ///
+    /// Overrides the optimizer settings of the config.
+    #[clap(long, conflicts_with = "num_of_optimizations")]
+    pub no_optimizer: bool,
+
+    /// The bytecode hash the contract was built with, e.g. `none`.
+    ///
+    /// Overrides the `bytecode_hash` of the config in the submitted compiler settings, since the
+    /// verifier can only fully match the metadata hash at the end of the bytecode if it compiles
+    /// with the same setting.
+    #[clap(long, value_name = "BYTECODE_HASH")]
+    pub compiler_metadata: Option<BytecodeHash>,
+
+    /// Read the compiler version, optimizer settings and EVM version from the given JSON file,
+    /// e.g. the solc metadata of a historical build.
+    ///
+    /// Overrides the config, explicitly passed compiler flags override the file.
+    #[clap(long, value_hint = ValueHint::FilePath, value_name = "PATH")]
+    pub metadata: Option<PathBuf>,
+
+    /// Verify the implementation of the EIP-1967 proxy deployed at the given address.
+    ///
+    /// The implementation address is read from the proxy's implementation slot.
+    #[clap(long)]
+    pub proxy: bool,
+
     /// Flatten the source code before verifying.
     #[clap(long)]
     pub flatten: bool,
 
-    /// Do not compile the flattened smart contract before verifying (if --flatten is passed).
+    /// Convert CRLF line endings of the source files to LF before submitting them.
+    ///
+    /// The sources are part of the metadata hash, so this only matches if the contract was
+    /// compiled from the normalized sources, e.g. on another machine.
+    #[clap(long)]
+    pub normalize_line_endings: bool,
+
+    /// Do not compile the flattened smart contract before verifying (if --flatten is passed), and
+    /// skip the safety checks.
+    ///
+    /// The safety checks compare the deployed bytecode and the compiler settings with the local
+    /// build. With `--force` they don't run at all, use `--yes` to run them and only print their
+    /// warnings.
     #[clap(short, long)]
     pub force: bool,
 
+    /// Acknowledge the warnings of the safety checks instead of failing, for non-interactive use,
+    /// e.g. in CI.
+    ///
+    /// Unlike `--force`, the checks still run, a bytecode mismatch or a compiler settings
+    /// difference is printed as a warning but doesn't stop the submission.
+    #[clap(long, short = 'y', env = "FOUNDRY_NONINTERACTIVE")]
+    pub yes: bool,
+
+    /// Recompile the contract even if its cached artifacts are up to date.
+    #[clap(long)]
+    pub recompile: bool,
+
     /// Do not check if the contract is already verified before verifying.
     #[clap(long)]
     pub skip_is_verified_check: bool,
@@ -88,10 +325,18 @@ pub struct VerifyArgs {
     /// The project's root path.
     ///
     /// By default root of the Git repository, if in one,
-    /// or the current working directory.
-    #[clap(long, value_hint = ValueHint::DirPath, value_name = "PATH")]
+    /// or the current working directory. Contract paths are resolved relative to the root, so the
+    /// command can be run from outside the project.
+    #[clap(long, value_hint = ValueHint::DirPath, value_name = "PATH", value_parser = parse_root)]
     pub root: Option<PathBuf>,
 
+    /// The path to the contract artifacts folder.
+    ///
+    /// Must match the artifacts folder the contract was compiled into, if it differs from the
+    /// configured `out` directory.
+    #[clap(long = "out", short, value_hint = ValueHint::DirPath, value_name = "PATH")]
+    pub out_path: Option<PathBuf>,
+
     /// Prints the standard json compiler input.
     ///
     /// The standard json compiler input can be used to manually submit contract verification in
@@ -99,9 +344,86 @@ pub struct VerifyArgs {
     #[clap(long, conflicts_with = "flatten")]
     pub show_standard_json_input: bool,
 
+    /// Write the standard json compiler input to the given file instead of printing it.
+    #[clap(
+        long,
+        requires = "show_standard_json_input",
+        value_hint = ValueHint::FilePath,
+        value_name = "PATH"
+    )]
+    pub standard_json_output: Option<PathBuf>,
+
+    /// Print where the on-chain creation code diverges from the locally compiled creation code,
+    /// without submitting the verification request.
+    ///
+    /// The creation code is fetched from the contract's creation transaction.
+    #[clap(long, conflicts_with_all = ["show_standard_json_input", "dry_run"])]
+    pub show_input: bool,
+
+    /// Print the metadata solc appended to the local and the on-chain code, without submitting the
+    /// verification request.
+    ///
+    /// Differing metadata, e.g. the IPFS hash, is why a contract only partially matches.
+    #[clap(long, conflicts_with_all = ["show_standard_json_input", "show_input", "dry_run"])]
+    pub show_metadata: bool,
+
+    /// Print the verification request that would be submitted to etherscan as JSON, without
+    /// submitting it.
+    #[clap(long, conflicts_with = "show_standard_json_input")]
+    pub dry_run: bool,
+
+    /// Write the verification request to the given file instead of submitting it.
+    ///
+    /// The file contains everything needed to submit the request later, e.g. from another machine,
+    /// with `--from`.
+    #[clap(
+        long,
+        conflicts_with_all = ["show_standard_json_input", "dry_run"],
+        value_hint = ValueHint::FilePath,
+        value_name = "PATH"
+    )]
+    pub export: Option<PathBuf>,
+
+    /// Submit a verification request that was written to the given file with `--export`.
+    ///
+    /// The contract address and identifier are read from the file.
+    #[clap(
+        long,
+        conflicts_with_all = ["export", "show_standard_json_input", "dry_run"],
+        value_hint = ValueHint::FilePath,
+        value_name = "PATH"
+    )]
+    pub from: Option<PathBuf>,
+
+    /// Print the verification result as a single JSON object.
+    ///
+    /// The object contains the `guid`, `status`, `message` and `url` of the verification request.
+    #[clap(long, conflicts_with = "watch")]
+    pub json: bool,
+
+    /// Only print the GUID of the submitted verification request.
+    ///
+    /// The status can be checked later with `forge verify-check <GUID>`. Errors are still printed
+    /// to stderr.
+    #[clap(long, conflicts_with_all = ["json", "watch"])]
+    pub guid_only: bool,
+
+    /// Don't print anything but errors, the outcome is reported by the exit code.
+    ///
+    /// Combined with `--guid-only`, only the GUID is printed.
+    #[clap(long, short, conflicts_with = "json")]
+    pub quiet: bool,
+
+    /// Do not use the cached chain ID of the RPC endpoint.
+    #[clap(long)]
+    pub no_cache: bool,
+
     #[clap(flatten)]
     pub etherscan: EtherscanOpts,
 
+    #[clap(flatten)]
+    pub rpc: RpcOpts,
+
     #[clap(flatten)]
     pub retry: RetryArgs,
 
@@ -111,6 +433,12 @@ pub struct VerifyArgs {
     /// Use the Yul intermediate representation compilation pipeline.
     #[clap(long)]
     pub via_ir: bool,
+
+    /// The SPDX identifier of the contract's license, e.g. `MIT`.
+    ///
+    /// By default the license is read from the `SPDX-License-Identifier` comment of the source.
+    #[clap(long, value_name = "SPDX", value_parser = parse_license)]
+    pub license: Option<String>,
 }
 
 impl_figment_convert!(VerifyArgs);
@@ -123,9 +451,13 @@ impl figment::Provider for VerifyArgs {
         &self,
     ) -> Result<figment::value::Map<figment::Profile, figment::value::Dict>, figment::Error> {
         let mut dict = self.etherscan.dict();
+        dict.extend(self.rpc.dict());
         if let Some(root) = self.root.as_ref() {
             dict.insert("root".to_string(), figment::value::Value::serialize(root)?);
         }
+        if let Some(out_path) = self.out_path.as_ref() {
+            dict.insert("out".to_string(), figment::value::Value::serialize(out_path)?);
+        }
         if let Some(optimizer_runs) = self.num_of_optimizations {
             dict.insert("optimizer".to_string(), figment::value::Value::serialize(true)?);
             dict.insert(
@@ -133,6 +465,15 @@ impl figment::Provider for VerifyArgs {
                 figment::value::Value::serialize(optimizer_runs)?,
             );
         }
+        if self.no_optimizer {
+            dict.insert("optimizer".to_string(), figment::value::Value::serialize(false)?);
+        }
+        if let Some(solc) = self.compiler_path.as_ref() {
+            dict.insert("solc".to_string(), figment::value::Value::serialize(solc)?);
+        }
+        if let Some(evm_version) = self.evm_version {
+            dict.insert("evm_version".to_string(), figment::value::Value::serialize(evm_version)?);
+        }
         if self.via_ir {
             dict.insert("via_ir".to_string(), figment::value::Value::serialize(self.via_ir)?);
         }
@@ -142,22 +483,116 @@ impl figment::Provider for VerifyArgs {
 
 impl VerifyArgs {
     /// Run the verify command to submit the contract's source code for verification on etherscan
-    pub async fn run(mut self) -> Result<()> {
-        let config = self.load_config_emit_warnings();
-        let chain = config.chain.unwrap_or_default();
-        self.etherscan.chain = Some(chain);
-        self.etherscan.key = config.get_etherscan_config_with_chain(Some(chain))?.map(|c| c.key);
+    pub async fn run(mut self) -> Result<VerifyResponse> {
+        if self.list_chains {
+            print_supported_chains();
+            return Ok(VerifyResponse::default())
+        }
+
+        let output = self.output_mode();
+        if let Some(path) = self.from.take() {
+            return self.submit_export(&path, output).await
+        }
+
+        if self.verifier.unique_verifiers().len() > 1 {
+            return self.run_verifiers().await
+        }
+
+        if let Some(path) = self.metadata.take() {
+            CompilerSettingsFile::read(&path)?.apply(&mut self);
+        }
+
+        let mut config = self.load_config_emit_warnings();
+        if config.chain.is_none() {
+            // only query the RPC endpoint if the chain wasn't provided
+            if let Some(rpc_url) = config.get_rpc_url().transpose()? {
+                let rpc_url = rpc_url.into_owned();
+                config.chain = Some(self.detect_chain(&mut config, &rpc_url).await?);
+            }
+        }
+        let chain = self.verifier.etherscan_api_version.resolve(&mut self.etherscan, &config)?;
+        if self.provider.is_none() && config.get_rpc_url().is_some() {
+            self.provider = Some(Arc::new(utils::get_provider(&config)?));
+        }
+        self.resolve_address(&config).await?;
+
+        if let Some(path) = self.artifact_path() {
+            if self.verifier.verifier() == VerificationProviderType::Sourcify {
+                eyre::bail!(
+                    "Verifying from the artifact {} is not supported for sourcify verification",
+                    path.display()
+                )
+            }
+            if self.flatten {
+                eyre::bail!(
+                    "`--flatten` can't be used when verifying from the artifact {}",
+                    path.display()
+                )
+            }
+        }
+
+        if self.proxy {
+            let proxy = self.address;
+            self.address = proxy_implementation(&self.provider(&config)?, proxy).await?;
+            if !self.json && shell::verbose_level() > 0 {
+                shell::println(format!("Proxy: {proxy}\nImplementation: {}", self.address))?;
+            }
+        }
 
         if self.show_standard_json_input {
             let args =
                 EtherscanVerificationProvider::default().create_verify_request(&self, None).await?;
-            println!("{}", args.source);
-            return Ok(())
+            if let Some(path) = &self.standard_json_output {
+                foundry_common::fs::write(path, &args.source)?;
+                println!("Standard json input written to {}", path.display());
+            } else {
+                println!("{}", args.source);
+            }
+            return Ok(VerifyResponse::default())
+        }
+
+        if self.show_input {
+            self.ensure_etherscan_verifier("--show-input")?;
+            EtherscanVerificationProvider::default().show_creation_code_diff(&self).await?;
+            return Ok(VerifyResponse::default())
+        }
+
+        if self.show_metadata {
+            EtherscanVerificationProvider::default().show_metadata(&self).await?;
+            return Ok(VerifyResponse::default())
+        }
+
+        if self.dry_run {
+            let args =
+                EtherscanVerificationProvider::default().create_verify_request(&self, None).await?;
+            println!("{}", serde_json::to_string_pretty(&args)?);
+            return Ok(VerifyResponse::default())
+        }
+
+        if let Some(path) = &self.export {
+            self.ensure_etherscan_verifier("--export")?;
+            let request =
+                EtherscanVerificationProvider::default().create_verify_request(&self, None).await?;
+            VerificationExport::new(chain, self.verifier.verifier_url.clone(), request)
+                .write(path)?;
+            println!("Verification request written to {}", path.display());
+            return Ok(VerifyResponse::default())
+        }
+
+        if config.get_rpc_url().is_some() {
+            ensure_contract_code(&self.provider(&config)?, self.address).await?;
         }
 
         let verifier_url = self.verifier.verifier_url.clone();
-        println!("Start verifying contract `{}` deployed on {chain}", self.address);
-        self.verifier.verifier.client(&self.etherscan.key)?.verify(self).await.map_err(|err| {
+        if !self.json && shell::verbose_level() > 0 {
+            shell::println(format!(
+                "Verifying {} at {} on {chain}",
+                self.contract.name, self.address
+            ))?;
+        }
+        let mut provider =
+            self.verifier.verifier().client(&self.etherscan.key, self.etherscan.chain)?;
+        let response = provider.verify(self).await.map_err(|err| {
             if let Some(verifier_url) = verifier_url {
                  match Url::parse(&verifier_url) {
                     Ok(url) => {
@@ -176,12 +611,274 @@ impl VerifyArgs {
             }
 
             err
-        })
+        })?;
+
+        output.print(&response)?;
+        Ok(response)
+    }
+
+    /// Verifies the contract with every configured verification provider, one after another, and
+    /// prints a report of the results.
+    ///
+    /// Fails only if the contract couldn't be verified by any of the providers. Returns the
+    /// response of the first provider that succeeded.
+    async fn run_verifiers(self) -> Result<VerifyResponse> {
+        let verifiers = self.verifier.unique_verifiers();
+
+        let mut results = Vec::with_capacity(verifiers.len());
+        for verifier in verifiers {
+            let mut args = self.clone();
+            args.verifier.verifiers = vec![verifier.clone()];
+            let result = Box::pin(args.run()).await;
+            if let Err(err) = &result {
+                eprintln!("Failed to verify with {verifier}: {err:#}");
+            }
+            results.push((verifier, result));
+        }
+
+        if !self.json {
+            println!("\nVerification report:");
+            for (verifier, result) in &results {
+                match result {
+                    Ok(response) if response.already_verified => {
+                        println!("  [Pass] {verifier} (already verified)")
+                    }
+                    Ok(_) => println!("  [Pass] {verifier}"),
+                    Err(err) => println!("  [Fail] {verifier}: {err}"),
+                }
+            }
+        }
+
+        let total = results.len();
+        let mut errors = Vec::new();
+        for (_, result) in results {
+            match result {
+                Ok(response) => return Ok(response),
+                Err(err) => errors.push(err),
+            }
+        }
+        let err = errors.pop().expect("at least two verifiers");
+        Err(err.wrap_err(format!("Failed to verify the contract with all {total} verifiers")))
+    }
+
+    /// Sets the address of the contract to verify, resolving an ENS name through the RPC
+    /// endpoint.
+    async fn resolve_address(&mut self, config: &Config) -> Result<()> {
+        match &self.address_or_name {
+            Some(NameOrAddress::Address(address)) => self.address = address.to_alloy(),
+            Some(NameOrAddress::Name(name)) => {
+                if config.get_rpc_url().is_none() {
+                    eyre::bail!(
+                        "An RPC URL is required to resolve the ENS name `{name}`, set it with `--rpc-url`"
+                    )
+                }
+                let address = self
+                    .provider(config)?
+                    .resolve_name(name)
+                    .await
+                    .wrap_err_with(|| format!("Failed to resolve the ENS name `{name}`"))?;
+                self.address = address.to_alloy();
+                if !self.json && shell::verbose_level() > 0 {
+                    shell::println(format!("Resolved {name} to {}", self.address))?;
+                }
+            }
+            None => {}
+        }
+        Ok(())
+    }
+
+    /// Submits the verification request exported to the given file with `--export`.
+    async fn submit_export(mut self, path: &Path, output: OutputMode) -> Result<VerifyResponse> {
+        self.ensure_etherscan_verifier("--from")?;
+        let export = VerificationExport::read(path)?;
+
+        let mut config = self.load_config_emit_warnings();
+        config.chain = Some(Chain::from_id(export.chain));
+        let chain = self.verifier.etherscan_api_version.resolve(&mut self.etherscan, &config)?;
+        if self.verifier.verifier_url.is_none() {
+            self.verifier.verifier_url = export.verifier_url;
+        }
+        self.address = export.request.address;
+
+        // ensures the API key is set
+        self.verifier.verifier().client(&self.etherscan.key, self.etherscan.chain)?;
+
+        if !self.json && shell::verbose_level() > 0 {
+            let name = export.request.contract_name.rsplit(':').next().unwrap_or_default();
+            shell::println(format!("Verifying {name} at {} on {chain}", self.address))?;
+        }
+        let response =
+            EtherscanVerificationProvider::default().verify_export(&self, export.request).await?;
+        output.print(&response)?;
+        Ok(response)
+    }
+
+    /// Returns how the result is printed once the request was submitted.
+    ///
+    /// `--guid-only` and `--quiet` suppress the regular output like `--json` does.
+    fn output_mode(&mut self) -> OutputMode {
+        if self.guid_only {
+            self.json = true;
+            OutputMode::GuidOnly
+        } else if self.quiet {
+            self.json = true;
+            OutputMode::Quiet
+        } else if self.json {
+            OutputMode::Json
+        } else {
+            OutputMode::Text
+        }
+    }
+
+    /// Returns the path of the compiled artifact, if the contract was given as
+    /// `out/<file>/<contractname>.json` instead of its identifier.
+    fn artifact_path(&self) -> Option<&Path> {
+        if self.contract.path.is_none() && self.contract.name.ends_with(".json") {
+            Some(Path::new(&self.contract.name))
+        } else {
+            None
+        }
+    }
+
+    /// Fails if the configured verifier doesn't use etherscan verification requests, which
+    /// `flag` requires.
+    fn ensure_etherscan_verifier(&self, flag: &str) -> Result<()> {
+        if self.verifier.verifier() == VerificationProviderType::Sourcify {
+            eyre::bail!("`{flag}` is not supported for sourcify verification")
+        }
+        Ok(())
+    }
+
+    /// Returns the chain of the RPC endpoint.
+    ///
+    /// The RPC URL may be a comma separated list of fallback endpoints, which are tried in order.
+    /// The first endpoint that responds is used for the rest of the command.
+    ///
+    /// The chain ID is cached in the project's cache directory, unless `--no-cache` is set.
+    async fn detect_chain(&mut self, config: &mut Config, rpc_url: &str) -> Result<Chain> {
+        let use_cache = config.cache && !self.no_cache;
+        let cache_path = config.cache_path.join(CHAIN_ID_CACHE_FILE);
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+        let urls =
+            rpc_url.split(',').map(str::trim).filter(|url| !url.is_empty()).collect::<Vec<_>>();
+        let mut cache =
+            if use_cache { ChainIdCache::read(&cache_path) } else { Default::default() };
+        if let Some((url, chain_id)) =
+            urls.iter().find_map(|url| cache.get(url, now).map(|chain_id| (url, chain_id)))
+        {
+            self.select_rpc_url(config, &urls, url);
+            return Ok(Chain::from_id(chain_id))
+        }
+
+        let mut errors = Vec::new();
+        for (idx, url) in urls.iter().enumerate() {
+            let mut endpoint_config = config.clone();
+            endpoint_config.eth_rpc_url = Some(url.to_string());
+            let provider = match utils::get_provider(&endpoint_config) {
+                Ok(provider) => provider,
+                Err(err) => {
+                    warn!(?err, endpoint = idx + 1, "failed to connect to RPC endpoint");
+                    errors.push(format!("RPC endpoint {}: {err}", idx + 1));
+                    continue
+                }
+            };
+            let chain = match utils::get_chain_id(&provider).await {
+                Ok(chain_id) => Chain::from_id(chain_id),
+                Err(err) => {
+                    warn!(?err, endpoint = idx + 1, "failed to detect the chain of RPC endpoint");
+                    errors.push(format!("RPC endpoint {}: {err}", idx + 1));
+                    continue
+                }
+            };
+
+            if use_cache {
+                cache.insert(url, chain.id(), now);
+                if let Err(err) = cache.write(&cache_path) {
+                    warn!(?err, "failed to write chain id cache");
+                }
+            }
+            self.select_rpc_url(config, &urls, url);
+            self.provider = Some(Arc::new(provider));
+            return Ok(chain)
+        }
+
+        eyre::bail!("Failed to detect the chain of the RPC endpoint:\n{}", errors.join("\n"))
+    }
+
+    /// Uses the given endpoint of the fallback RPC URLs for the rest of the command.
+    fn select_rpc_url(&mut self, config: &mut Config, urls: &[&str], url: &str) {
+        if urls.len() > 1 {
+            self.rpc.url = Some(url.to_string());
+            config.eth_rpc_url = Some(url.to_string());
+        }
+    }
+
+    /// Returns the provider of the RPC endpoint.
+    ///
+    /// `run` connects once and shares the provider, it's only created here if the arguments are
+    /// used without `run`.
+    pub fn provider(&self, config: &Config) -> Result<Arc<RetryProvider>> {
+        match &self.provider {
+            Some(provider) => Ok(provider.clone()),
+            None => Ok(Arc::new(utils::get_provider(config)?)),
+        }
     }
 
     /// Returns the configured verification provider
     pub fn verification_provider(&self) -> Result<Box<dyn VerificationProvider>> {
-        self.verifier.verifier.client(&self.etherscan.key)
+        self.verifier.verifier().client(&self.etherscan.key, self.etherscan.chain)
+    }
+}
+
+/// How the result of `forge verify-contract` is printed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum OutputMode {
+    /// The human readable output is printed while verifying.
+    Text,
+    /// `--json`
+    Json,
+    /// `--guid-only`
+    GuidOnly,
+    /// `--quiet`
+    Quiet,
+}
+
+impl OutputMode {
+    /// Prints the result of the verification request, unless it was already printed as text.
+    fn print(self, response: &VerifyResponse) -> Result<()> {
+        match self {
+            Self::Text | Self::Quiet => {}
+            Self::Json => {
+                println!("{}", serde_json::to_string(&VerifyJsonOutput::from(response))?)
+            }
+            Self::GuidOnly => match &response.guid {
+                Some(guid) => println!("{guid}"),
+                None if response.already_verified => eprintln!("Contract is already verified"),
+                None => {}
+            },
+        }
+        Ok(())
+    }
+}
+
+/// The `--json` output of `forge verify-contract`.
+#[derive(Debug, Serialize)]
+struct VerifyJsonOutput<'a> {
+    guid: Option<&'a str>,
+    status: &'static str,
+    message: Option<&'a str>,
+    url: Option<&'a str>,
+}
+
+impl<'a> From<&'a VerifyResponse> for VerifyJsonOutput<'a> {
+    fn from(response: &'a VerifyResponse) -> Self {
+        Self {
+            guid: response.guid.as_deref(),
+            status: if response.already_verified { "already_verified" } else { "submitted" },
+            message: response.message.as_deref(),
+            url: response.url.as_deref(),
+        }
     }
 }
 
@@ -203,15 +900,28 @@ pub struct VerifyCheckArgs {
 
     #[clap(flatten)]
     verifier: VerifierArgs,
+
+    /// Don't print the verification status, the outcome is reported by the exit code.
+    #[clap(long, short)]
+    quiet: bool,
 }
 
 impl_figment_convert_cast!(VerifyCheckArgs);
 
 impl VerifyCheckArgs {
     /// Run the verify command to submit the contract's source code for verification on etherscan
-    pub async fn run(self) -> Result<()> {
-        println!("Checking verification status on {}", self.etherscan.chain.unwrap_or_default());
-        self.verifier.verifier.client(&self.etherscan.key)?.check(self).await
+    pub async fn run(mut self) -> Result<()> {
+        let config = self.load_config_emit_warnings();
+        let chain = self.verifier.etherscan_api_version.resolve(&mut self.etherscan, &config)?;
+
+        if !self.quiet && shell::verbose_level() > 0 {
+            shell::println(format!("Checking verification status on {chain}"))?;
+        }
+        self.verifier
+            .verifier()
+            .client(&self.etherscan.key, self.etherscan.chain)?
+            .check(self)
+            .await
     }
 }
 
@@ -227,6 +937,74 @@ impl figment::Provider for VerifyCheckArgs {
     }
 }
 
+/// Prints the ID, name and API key env var of every chain supported by etherscan verification.
+fn print_supported_chains() {
+    for chain in etherscan::etherscan_supported_chains() {
+        let api_key = chain.etherscan_api_key_name().unwrap_or("ETHERSCAN_API_KEY");
+        println!("{:<12} {:<28} {api_key}", chain as u64, chain.to_string());
+    }
+}
+
+/// Reads the implementation address of the EIP-1967 proxy deployed at `proxy`.
+async fn proxy_implementation(provider: &RetryProvider, proxy: Address) -> Result<Address> {
+    let value = provider
+        .get_storage_at(proxy.to_ethers(), EIP1967_IMPLEMENTATION_SLOT.to_ethers(), None)
+        .await?;
+    let implementation = Address::from_word(value.to_alloy());
+    if implementation == Address::ZERO {
+        eyre::bail!("No EIP-1967 implementation set for proxy {proxy}")
+    }
+    Ok(implementation)
+}
+
+/// Parses the project root, which is made absolute so that the paths of the project match the
+/// paths in its cache, no matter the working directory.
+fn parse_root(root: &str) -> Result<PathBuf> {
+    foundry_compilers::utils::canonicalize(root)
+        .wrap_err_with(|| format!("Project root `{root}` doesn't exist"))
+}
+
+/// Parses the number of optimizer runs, `auto` is solc's default.
+fn parse_optimizer_runs(runs: &str) -> Result<usize> {
+    if runs.eq_ignore_ascii_case("auto") {
+        return Ok(SOLC_DEFAULT_OPTIMIZER_RUNS)
+    }
+    runs.parse().wrap_err_with(|| format!("Invalid number of optimizer runs `{runs}`"))
+}
+
+/// Parses an SPDX license identifier that etherscan supports.
+fn parse_license(spdx: &str) -> Result<String> {
+    if etherscan::license::license_type(spdx).is_none() {
+        eyre::bail!(
+            "Unsupported license `{spdx}`, supported licenses are: {}",
+            etherscan::license::supported_licenses()
+        )
+    }
+    Ok(spdx.to_string())
+}
+
+/// Parses a hex address or an ENS name like `vitalik.eth`.
+fn parse_address_or_name(s: &str) -> Result<NameOrAddress> {
+    if s.starts_with("0x") {
+        return Ok(NameOrAddress::Address(s.parse()?))
+    }
+    if !s.contains('.') || s.contains(['/', ':']) {
+        eyre::bail!("`{s}` is neither an address nor an ENS name")
+    }
+    Ok(NameOrAddress::Name(s.to_string()))
+}
+
+/// Fails if there's no contract deployed at `address`, e.g. because it's a wallet address.
+async fn ensure_contract_code(provider: &RetryProvider, address: Address) -> Result<()> {
+    let code = provider.get_code(address.to_ethers(), None).await?;
+    if code.is_empty() {
+        eyre::bail!(
+            "No contract code at {address}, it's either not a contract or not deployed yet on this chain"
+        )
+    }
+    Ok(())
+}
+
 /// Returns `true` if the URL only consists of host.
 ///
 /// This is used to check user input url for missing /api path
@@ -256,4 +1034,518 @@ mod tests {
         ]);
         assert!(args.via_ir);
     }
+
+    #[test]
+    fn can_set_custom_out_dir() {
+        let args: VerifyArgs = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000000",
+            "src/Domains.sol:Domains",
+            "--out",
+            "custom-out",
+        ]);
+        let config = Config::from(&args);
+        assert_eq!(config.out, PathBuf::from("custom-out"));
+        assert!(config.project_paths().artifacts.ends_with("custom-out"));
+    }
+
+    #[test]
+    fn can_override_optimizer_settings() {
+        let args: VerifyArgs = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000000",
+            "src/Domains.sol:Domains",
+            "--num-of-optimizations",
+            "1000",
+        ]);
+        let config = Config::from(&args);
+        assert!(config.optimizer);
+        assert_eq!(config.optimizer_runs, 1000);
+
+        let args: VerifyArgs = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000000",
+            "src/Domains.sol:Domains",
+            "--no-optimizer",
+        ]);
+        assert!(!Config::from(&args).optimizer);
+
+        let res = VerifyArgs::try_parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000000",
+            "src/Domains.sol:Domains",
+            "--no-optimizer",
+            "--optimizer-runs",
+            "200",
+        ]);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn can_parse_constructor_args_json() {
+        let args: VerifyArgs = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000000",
+            "src/Domains.sol:Domains",
+            "--constructor-args-json",
+            r#"[[1, 2], ["0x0000000000000000000000000000000000000001", true]]"#,
+        ]);
+        assert!(args.constructor_args_json.is_some());
+
+        let res = VerifyArgs::try_parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000000",
+            "src/Domains.sol:Domains",
+            "--constructor-args-json",
+            "[]",
+            "--constructor-args",
+            "0x",
+        ]);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn can_parse_no_constructor_args() {
+        let args: VerifyArgs = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000000",
+            "src/Counter.sol:Counter",
+            "--no-constructor-args",
+        ]);
+        assert!(args.no_constructor_args);
+
+        let res = VerifyArgs::try_parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000000",
+            "src/Counter.sol:Counter",
+            "--no-constructor-args",
+            "--constructor-args",
+            "0x01",
+        ]);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn can_parse_license() {
+        let args: VerifyArgs = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000001",
+            "src/Counter.sol:Counter",
+            "--license",
+            "Apache-2.0",
+        ]);
+        assert_eq!(args.license.as_deref(), Some("Apache-2.0"));
+
+        let err = VerifyArgs::try_parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000001",
+            "src/Counter.sol:Counter",
+            "--license",
+            "WTFPL",
+        ])
+        .unwrap_err();
+        assert!(err.to_string().contains("Unsupported license `WTFPL`"));
+    }
+
+    #[test]
+    fn can_parse_relative_root() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path().join("contracts");
+        std::fs::create_dir_all(&root).unwrap();
+        let relative = root.join("..").join("contracts");
+
+        let args: VerifyArgs = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000001",
+            "src/Counter.sol:Counter",
+            "--root",
+            relative.to_str().unwrap(),
+        ]);
+        assert_eq!(args.root, Some(foundry_compilers::utils::canonicalize(&root).unwrap()));
+
+        let err = VerifyArgs::try_parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000001",
+            "src/Counter.sol:Counter",
+            "--root",
+            temp.path().join("missing").to_str().unwrap(),
+        ])
+        .unwrap_err();
+        assert!(err.to_string().contains("doesn't exist"));
+    }
+
+    #[test]
+    fn can_parse_artifact_path() {
+        let args: VerifyArgs = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000001",
+            "out/Counter.sol/Counter.json",
+        ]);
+        assert_eq!(args.artifact_path(), Some(Path::new("out/Counter.sol/Counter.json")));
+
+        let args: VerifyArgs = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000001",
+            "src/Counter.sol:Counter",
+        ]);
+        assert_eq!(args.artifact_path(), None);
+    }
+
+    #[tokio::test]
+    async fn can_parse_address_or_ens_name() {
+        let mut args: VerifyArgs = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000001",
+            "src/Counter.sol:Counter",
+        ]);
+        args.resolve_address(&Config::default()).await.unwrap();
+        assert_eq!(args.address, Address::with_last_byte(1));
+
+        let mut args: VerifyArgs =
+            VerifyArgs::parse_from(["foundry-cli", "counter.eth", "src/Counter.sol:Counter"]);
+        assert_eq!(args.address_or_name, Some(NameOrAddress::Name("counter.eth".to_string())));
+        assert!(parse_address_or_name("src/Counter.sol:Counter").is_err());
+
+        let err = args.resolve_address(&Config::default()).await.unwrap_err();
+        assert!(err.to_string().starts_with("An RPC URL is required to resolve the ENS name"));
+    }
+
+    #[test]
+    fn can_parse_quiet() {
+        let mut args: VerifyArgs = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000001",
+            "src/Counter.sol:Counter",
+            "--quiet",
+            "--watch",
+        ]);
+        assert_eq!(args.output_mode(), OutputMode::Quiet);
+        assert!(args.json);
+
+        let mut args: VerifyArgs = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000001",
+            "src/Counter.sol:Counter",
+            "--quiet",
+            "--guid-only",
+        ]);
+        assert_eq!(args.output_mode(), OutputMode::GuidOnly);
+
+        assert!(VerifyArgs::try_parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000001",
+            "src/Counter.sol:Counter",
+            "--quiet",
+            "--json",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn can_parse_auto_optimizer_runs() {
+        let args: VerifyArgs = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000001",
+            "src/Counter.sol:Counter",
+            "--optimizer-runs",
+            "auto",
+        ]);
+        assert_eq!(args.num_of_optimizations, Some(SOLC_DEFAULT_OPTIMIZER_RUNS));
+
+        assert!(VerifyArgs::try_parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000001",
+            "src/Counter.sol:Counter",
+            "--optimizer-runs",
+            "many",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn can_parse_recompile() {
+        let args: VerifyArgs = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000001",
+            "src/Counter.sol:Counter",
+        ]);
+        assert!(!args.recompile);
+
+        let args: VerifyArgs = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000001",
+            "src/Counter.sol:Counter",
+            "--recompile",
+        ]);
+        assert!(args.recompile);
+        assert!(!args.force);
+    }
+
+    #[test]
+    fn can_parse_yes() {
+        let args: VerifyArgs = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000001",
+            "src/Counter.sol:Counter",
+            "-y",
+        ]);
+        assert!(args.yes);
+        assert!(!args.force);
+    }
+
+    #[test]
+    fn can_parse_guid_only() {
+        let mut args: VerifyArgs = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000000",
+            "src/Counter.sol:Counter",
+            "--guid-only",
+        ]);
+        assert_eq!(args.output_mode(), OutputMode::GuidOnly);
+        assert!(args.json);
+
+        let res = VerifyArgs::try_parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000000",
+            "src/Counter.sol:Counter",
+            "--guid-only",
+            "--json",
+        ]);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn blockscout_requires_verifier_url() {
+        let res = VerifyArgs::try_parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000000",
+            "src/Domains.sol:Domains",
+            "--verifier",
+            "blockscout",
+        ]);
+        if std::env::var_os("VERIFIER_URL").is_none() {
+            assert!(res.is_err());
+        }
+
+        let args: VerifyArgs = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000000",
+            "src/Domains.sol:Domains",
+            "--verifier",
+            "blockscout",
+            "--verifier-url",
+            "https://eth.blockscout.com/api",
+        ]);
+        assert_eq!(args.verifier.verifier(), VerificationProviderType::Blockscout);
+        assert_eq!(args.verifier.verifier_url.as_deref(), Some("https://eth.blockscout.com/api"));
+
+        let args: VerifyArgs = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000000",
+            "src/Domains.sol:Domains",
+            "--verifier",
+            "etherscan",
+            "--verifier",
+            "sourcify",
+        ]);
+        assert_eq!(
+            args.verifier.verifiers,
+            vec![VerificationProviderType::Etherscan, VerificationProviderType::Sourcify]
+        );
+        assert_eq!(args.verifier.verifier(), VerificationProviderType::Etherscan);
+
+        let args: VerifyArgs = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000000",
+            "src/Domains.sol:Domains",
+            "--verifier",
+            "etherscan",
+            "--verifier",
+            "sourcify",
+            "--verifier",
+            "etherscan",
+        ]);
+        assert_eq!(
+            args.verifier.unique_verifiers(),
+            vec![VerificationProviderType::Etherscan, VerificationProviderType::Sourcify]
+        );
+    }
+
+    #[tokio::test]
+    async fn uses_first_cached_fallback_rpc_url() {
+        let temp = tempfile::tempdir().unwrap();
+        let mut config = Config { cache_path: temp.path().to_path_buf(), ..Default::default() };
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        let mut cache = ChainIdCache::default();
+        cache.insert("http://localhost:8546", 10, now);
+        cache.write(&config.cache_path.join(CHAIN_ID_CACHE_FILE)).unwrap();
+
+        let mut args: VerifyArgs = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000000",
+            "src/Domains.sol:Domains",
+        ]);
+        let chain = args
+            .detect_chain(&mut config, "http://localhost:8545, http://localhost:8546")
+            .await
+            .unwrap();
+        assert_eq!(chain.id(), 10);
+        assert_eq!(args.rpc.url.as_deref(), Some("http://localhost:8546"));
+        assert_eq!(config.eth_rpc_url.as_deref(), Some("http://localhost:8546"));
+    }
+
+    #[test]
+    fn eip1967_implementation_slot() {
+        let slot = alloy_primitives::keccak256("eip1967.proxy.implementation");
+        let slot = alloy_primitives::U256::from_be_bytes(slot.0) - alloy_primitives::U256::from(1);
+        assert_eq!(B256::from(slot), EIP1967_IMPLEMENTATION_SLOT);
+    }
+
+    #[test]
+    fn can_parse_standard_json_output() {
+        let args: VerifyArgs = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000000",
+            "src/Domains.sol:Domains",
+            "--show-standard-json-input",
+            "--standard-json-output",
+            "input.json",
+        ]);
+        assert_eq!(args.standard_json_output, Some(PathBuf::from("input.json")));
+
+        let res = VerifyArgs::try_parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000000",
+            "src/Domains.sol:Domains",
+            "--standard-json-output",
+            "input.json",
+        ]);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn can_parse_list_chains() {
+        let args: VerifyArgs = VerifyArgs::parse_from(["foundry-cli", "--list-chains"]);
+        assert!(args.list_chains);
+
+        assert!(VerifyArgs::try_parse_from(["foundry-cli"]).is_err());
+        assert!(VerifyArgs::try_parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000000",
+            "src/Domains.sol:Domains",
+            "--list-chains",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn can_parse_dry_run() {
+        let args: VerifyArgs = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000000",
+            "src/Domains.sol:Domains",
+            "--dry-run",
+        ]);
+        assert!(args.dry_run);
+
+        let res = VerifyArgs::try_parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000000",
+            "src/Domains.sol:Domains",
+            "--dry-run",
+            "--show-standard-json-input",
+        ]);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn can_parse_export_and_from() {
+        let args: VerifyArgs = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000000",
+            "src/Domains.sol:Domains",
+            "--export",
+            "verify.json",
+        ]);
+        assert_eq!(args.export, Some(PathBuf::from("verify.json")));
+
+        // the address and contract are part of the exported request
+        let args: VerifyArgs = VerifyArgs::parse_from(["foundry-cli", "--from", "verify.json"]);
+        assert_eq!(args.from, Some(PathBuf::from("verify.json")));
+
+        let res = VerifyArgs::try_parse_from([
+            "foundry-cli",
+            "--from",
+            "verify.json",
+            "--export",
+            "verify.json",
+        ]);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn can_parse_verify_check() {
+        let args: VerifyCheckArgs = VerifyCheckArgs::parse_from([
+            "foundry-cli",
+            "ezqkv9pdhqmecvhgjbm1ehmgjvxecbqnnxhfzrt1rygxmjkeft",
+            "--chain",
+            "sepolia",
+        ]);
+        assert_eq!(args.id, "ezqkv9pdhqmecvhgjbm1ehmgjvxecbqnnxhfzrt1rygxmjkeft");
+        assert_eq!(
+            args.etherscan.chain,
+            Some(Chain::from_named(foundry_config::NamedChain::Sepolia))
+        );
+    }
+
+    #[test]
+    fn requires_etherscan_api_key() {
+        let err = VerificationProviderType::Etherscan.client(&None, None).err().unwrap();
+        assert!(err.to_string().starts_with("ETHERSCAN_API_KEY must be set."));
+        assert!(VerificationProviderType::Etherscan.client(&Some(String::new()), None).is_err());
+        assert!(VerificationProviderType::Sourcify.client(&None, None).is_ok());
+
+        let polygon = Some(Chain::from_named(foundry_config::NamedChain::Polygon));
+        let err = VerificationProviderType::Etherscan.client(&None, polygon).err().unwrap();
+        assert!(err.to_string().starts_with("POLYGONSCAN_API_KEY must be set."));
+    }
+
+    #[test]
+    fn rejects_malformed_etherscan_api_key() {
+        let client =
+            |key: &str| VerificationProviderType::Etherscan.client(&Some(key.into()), None);
+        assert!(client("ABCDEFGHIJKLMNOPQRSTUVWXYZ12345678").is_ok());
+        assert!(client("ABCDEFGHIJKLMNOPQRSTUVWXYZ12345678, ZYXWVUTSRQPONMLKJIHGFEDCBA87654321")
+            .is_ok());
+
+        let err = client("ABCDEFGHIJKLMNOPQRSTUVWXYZ12345678\n").err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "The etherscan API key contains whitespace, set a valid ETHERSCAN_API_KEY"
+        );
+        assert!(client(&"A".repeat(129)).is_err());
+    }
+
+    #[test]
+    fn can_serialize_json_output() {
+        let response = VerifyResponse {
+            guid: None,
+            url: Some(
+                "https://etherscan.io/address/0x0000000000000000000000000000000000000000"
+                    .to_string(),
+            ),
+            message: Some("Contract source code already verified".to_string()),
+            already_verified: true,
+        };
+        let json = serde_json::to_value(VerifyJsonOutput::from(&response)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "guid": null,
+                "status": "already_verified",
+                "message": "Contract source code already verified",
+                "url": "https://etherscan.io/address/0x0000000000000000000000000000000000000000",
+            })
+        );
+    }
 }
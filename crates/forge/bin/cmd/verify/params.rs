@@ -0,0 +1,173 @@
+//! Programmatic contract verification.
+//!
+//! [`verify_contract`] submits a contract for verification without going through the
+//! `forge verify-contract` argument parser, e.g. for `forge script --verify`.
+
+use super::{provider::VerifyResponse, VerifierArgs, VerifyArgs};
+use crate::cmd::retry::RetryArgs;
+use alloy_primitives::Address;
+use eyre::Result;
+use foundry_cli::opts::{EtherscanOpts, RpcOpts};
+use foundry_compilers::{info::ContractInfo, EvmVersion};
+use foundry_config::Chain;
+use std::path::PathBuf;
+
+/// The parameters of a verification request.
+#[derive(Clone, Debug)]
+pub struct VerifyParams {
+    /// The address of the contract to verify.
+    pub address: Address,
+    /// The contract identifier, in the form `<path>:<contractname>` or `<contractname>`.
+    pub contract: ContractInfo,
+    /// The chain the contract is deployed on, defaults to the configured chain.
+    pub chain: Option<Chain>,
+    /// The etherscan API key, defaults to the configured key of the chain.
+    pub etherscan_key: Option<String>,
+    /// The RPC endpoint the contract is deployed to, used to detect the chain and to compare the
+    /// deployed bytecode.
+    pub rpc: RpcOpts,
+    /// The verification provider to use.
+    pub verifier: VerifierArgs,
+    /// The ABI-encoded constructor arguments, as a hex string.
+    pub constructor_args: Option<String>,
+    /// The compiler version used to build the contract, defaults to the version in the cache.
+    pub compiler_version: Option<String>,
+    /// The EVM version the contract was compiled for.
+    pub evm_version: Option<EvmVersion>,
+    /// The number of optimization runs used to build the contract.
+    pub num_of_optimizations: Option<usize>,
+    /// Whether the contract was compiled with the IR pipeline.
+    pub via_ir: bool,
+    /// The linked libraries, in the form `<path>:<contractname>:<address>`.
+    pub libraries: Vec<String>,
+    /// The project root, defaults to the current directory.
+    pub root: Option<PathBuf>,
+    /// Whether to flatten the source code before verifying.
+    pub flatten: bool,
+    /// Whether to skip the bytecode comparison with the deployed contract.
+    pub force: bool,
+    /// Whether to skip the check if the contract is already verified.
+    pub skip_is_verified_check: bool,
+    /// Whether to wait for the verification result.
+    pub watch: bool,
+    /// Whether to print the standard json input that is submitted.
+    pub show_standard_json_input: bool,
+    /// Retry options for submitting the request and polling its status.
+    pub retry: RetryArgs,
+}
+
+impl VerifyParams {
+    /// Creates the parameters to verify the contract at `address` with the default settings.
+    pub fn new(address: Address, contract: ContractInfo) -> Self {
+        Self {
+            address,
+            contract,
+            chain: None,
+            etherscan_key: None,
+            rpc: Default::default(),
+            verifier: Default::default(),
+            constructor_args: None,
+            compiler_version: None,
+            evm_version: None,
+            num_of_optimizations: None,
+            via_ir: false,
+            libraries: vec![],
+            root: None,
+            flatten: false,
+            force: false,
+            skip_is_verified_check: false,
+            watch: true,
+            show_standard_json_input: false,
+            retry: Default::default(),
+        }
+    }
+}
+
+impl From<VerifyParams> for VerifyArgs {
+    fn from(params: VerifyParams) -> Self {
+        VerifyArgs {
+            address_or_name: None,
+            address: params.address,
+            provider: None,
+            etherscan_clients: Default::default(),
+            contract: params.contract,
+            compiler_version: params.compiler_version,
+            compiler_path: None,
+            constructor_args: params.constructor_args,
+            constructor_args_path: None,
+            constructor_args_json: None,
+            no_constructor_args: false,
+            evm_version: params.evm_version,
+            guess_constructor_args: false,
+            num_of_optimizations: params.num_of_optimizations,
+            no_optimizer: false,
+            compiler_metadata: None,
+            metadata: None,
+            etherscan: EtherscanOpts { key: params.etherscan_key, chain: params.chain },
+            rpc: params.rpc,
+            list_chains: false,
+            proxy: false,
+            flatten: params.flatten,
+            normalize_line_endings: false,
+            force: params.force,
+            recompile: false,
+            yes: false,
+            skip_is_verified_check: params.skip_is_verified_check,
+            watch: params.watch,
+            retry: params.retry,
+            libraries: params.libraries,
+            root: params.root,
+            out_path: None,
+            verifier: params.verifier,
+            via_ir: params.via_ir,
+            license: None,
+            show_standard_json_input: params.show_standard_json_input,
+            standard_json_output: None,
+            show_input: false,
+            show_metadata: false,
+            dry_run: false,
+            export: None,
+            from: None,
+            json: false,
+            guid_only: false,
+            quiet: false,
+            no_cache: false,
+        }
+    }
+}
+
+/// Submits the contract for verification.
+///
+/// This behaves like `forge verify-contract` with the given parameters.
+/// The common verification failures can be told apart by downcasting the error to a
+/// [`VerifyError`](super::VerifyError).
+pub async fn verify_contract(params: VerifyParams) -> Result<VerifyResponse> {
+    VerifyArgs::from(params).run().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use foundry_config::NamedChain;
+
+    #[test]
+    fn can_convert_params_to_args() {
+        let mut params = VerifyParams::new(
+            Address::with_last_byte(1),
+            "src/Counter.sol:Counter".parse().unwrap(),
+        );
+        params.chain = Some(NamedChain::Sepolia.into());
+        params.constructor_args = Some("01".to_string());
+        params.num_of_optimizations = Some(200);
+
+        let args = VerifyArgs::from(params);
+        assert_eq!(args.address, Address::with_last_byte(1));
+        assert_eq!(args.contract.path.as_deref(), Some("src/Counter.sol"));
+        assert_eq!(args.contract.name, "Counter");
+        assert_eq!(args.etherscan.chain, Some(NamedChain::Sepolia.into()));
+        assert_eq!(args.constructor_args.as_deref(), Some("01"));
+        assert_eq!(args.num_of_optimizations, Some(200));
+        assert!(args.watch);
+        assert!(!args.skip_is_verified_check);
+    }
+}
@@ -4,8 +4,14 @@ use super::{
 };
 use async_trait::async_trait;
 use eyre::Result;
+use foundry_config::Chain;
 use std::{fmt, str::FromStr};
 
+/// The maximum length of an etherscan API key that is considered valid.
+///
+/// Etherscan keys are 34 characters long, but compatible explorers use different formats.
+const MAX_ETHERSCAN_API_KEY_LEN: usize = 128;
+
 /// An abstraction for various verification providers such as etherscan, sourcify, blockscout
 #[async_trait]
 pub trait VerificationProvider {
@@ -19,12 +25,25 @@ pub trait VerificationProvider {
     async fn preflight_check(&mut self, args: VerifyArgs) -> Result<()>;
 
     /// Sends the actual verify request for the targeted contract.
-    async fn verify(&mut self, args: VerifyArgs) -> Result<()>;
+    async fn verify(&mut self, args: VerifyArgs) -> Result<VerifyResponse>;
 
     /// Checks whether the contract is verified.
     async fn check(&self, args: VerifyCheckArgs) -> Result<()>;
 }
 
+/// The outcome of a [`VerificationProvider::verify`] request.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VerifyResponse {
+    /// The submission GUID, if the verification request was submitted to etherscan.
+    pub guid: Option<String>,
+    /// The URL of the contract on the block explorer, if known.
+    pub url: Option<String>,
+    /// The message returned by the verification provider, if any.
+    pub message: Option<String>,
+    /// Whether the contract was already verified.
+    pub already_verified: bool,
+}
+
 impl FromStr for VerificationProviderType {
     type Err = String;
 
@@ -55,7 +74,7 @@ impl fmt::Display for VerificationProviderType {
     }
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, clap::ValueEnum)]
 pub enum VerificationProviderType {
     #[default]
     Etherscan,
@@ -65,12 +84,16 @@ pub enum VerificationProviderType {
 
 impl VerificationProviderType {
     /// Returns the corresponding `VerificationProvider` for the key
-    pub fn client(&self, key: &Option<String>) -> Result<Box<dyn VerificationProvider>> {
+    ///
+    /// The chain is used to point to the explorer specific API key env var if the key is invalid.
+    pub fn client(
+        &self,
+        key: &Option<String>,
+        chain: Option<Chain>,
+    ) -> Result<Box<dyn VerificationProvider>> {
         match self {
             VerificationProviderType::Etherscan => {
-                if key.as_ref().map_or(true, |key| key.is_empty()) {
-                    eyre::bail!("ETHERSCAN_API_KEY must be set")
-                }
+                validate_etherscan_api_key(key.as_deref(), chain)?;
                 Ok(Box::<EtherscanVerificationProvider>::default())
             }
             VerificationProviderType::Sourcify => {
@@ -82,3 +105,36 @@ impl VerificationProviderType {
         }
     }
 }
+
+/// Ensures the etherscan API key is set and well-formed, before anything is compiled or sent.
+fn validate_etherscan_api_key(key: Option<&str>, chain: Option<Chain>) -> Result<()> {
+    let var = chain
+        .and_then(|chain| chain.named())
+        .and_then(|chain| chain.etherscan_api_key_name())
+        .unwrap_or("ETHERSCAN_API_KEY");
+
+    let Some(key) = key.filter(|key| !key.is_empty()) else {
+        eyre::bail!(
+            "{var} must be set. The API key is resolved from, in order:
+  1. `--etherscan-api-key`
+  2. the chain specific env var of the explorer, e.g. `POLYGONSCAN_API_KEY`
+  3. the `ETHERSCAN_API_KEY` env var
+  4. `etherscan_api_key` in foundry.toml
+  5. the entry of the `[etherscan]` table in foundry.toml matching the chain
+With `--etherscan-api-version v2`, the chain specific env var is only used after 3 and 4."
+        )
+    };
+
+    // several keys can be passed as a comma separated list
+    for key in key.split(',').map(|key| key.trim_matches(' ')).filter(|key| !key.is_empty()) {
+        if key.chars().any(char::is_whitespace) {
+            eyre::bail!("The etherscan API key contains whitespace, set a valid {var}")
+        }
+        if key.len() > MAX_ETHERSCAN_API_KEY_LEN {
+            eyre::bail!(
+                "The etherscan API key is longer than {MAX_ETHERSCAN_API_KEY_LEN} characters, set a valid {var}"
+            )
+        }
+    }
+    Ok(())
+}
@@ -1,4 +1,7 @@
-use super::{provider::VerificationProvider, VerifyArgs, VerifyCheckArgs};
+use super::{
+    provider::{VerificationProvider, VerifyResponse},
+    VerifyArgs, VerifyCheckArgs,
+};
 use async_trait::async_trait;
 use eyre::Result;
 use foundry_cli::utils::{get_cached_entry_by_name, LoadConfig};
@@ -6,7 +9,7 @@ use foundry_common::{fs, retry::Retry};
 use foundry_compilers::ConfigurableContractArtifact;
 use futures::FutureExt;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, path::PathBuf};
+use std::collections::HashMap;
 
 pub static SOURCIFY_URL: &str = "https://sourcify.dev/server/";
 
@@ -22,7 +25,7 @@ impl VerificationProvider for SourcifyVerificationProvider {
         Ok(())
     }
 
-    async fn verify(&mut self, args: VerifyArgs) -> Result<()> {
+    async fn verify(&mut self, args: VerifyArgs) -> Result<VerifyResponse> {
         let body = self.prepare_request(&args)?;
 
         trace!("submitting verification request {:?}", body);
@@ -33,11 +36,13 @@ impl VerificationProvider for SourcifyVerificationProvider {
         let resp = retry
             .run_async(|| {
                 async {
-                    println!(
-                        "\nSubmitting verification for [{}] {:?}.",
-                        args.contract.name,
-                        args.address.to_string()
-                    );
+                    if !args.json {
+                        println!(
+                            "\nSubmitting verification for [{}] {:?}.",
+                            args.contract.name,
+                            args.address.to_string()
+                        );
+                    }
                     let response = client
                         .post(args.verifier.verifier_url.as_deref().unwrap_or(SOURCIFY_URL))
                         .header("Content-Type", "application/json")
@@ -61,7 +66,19 @@ impl VerificationProvider for SourcifyVerificationProvider {
             })
             .await?;
 
-        self.process_sourcify_response(resp.map(|r| r.result))
+        // sourcify only reports a storage timestamp for contracts that were verified before
+        let already_verified = resp
+            .as_ref()
+            .and_then(|resp| resp.result.first())
+            .is_some_and(|resp| resp.storage_timestamp.is_some());
+        let message = self.process_sourcify_response(resp.map(|r| r.result))?;
+        if let Some(message) = &message {
+            if !args.json {
+                println!("{message}");
+            }
+        }
+
+        Ok(VerifyResponse { guid: None, url: None, message, already_verified })
     }
 
     async fn check(&self, args: VerifyCheckArgs) -> Result<()> {
@@ -90,7 +107,12 @@ impl VerificationProvider for SourcifyVerificationProvider {
             })
             .await?;
 
-        self.process_sourcify_response(resp)
+        if let Some(message) = self.process_sourcify_response(resp)? {
+            if !args.quiet {
+                println!("{message}");
+            }
+        }
+        Ok(())
     }
 }
 
@@ -139,7 +161,8 @@ metadata output can be enabled via `extra_output = ["metadata"]` in `foundry.tom
             )
         }
 
-        let contract_path = args.contract.path.clone().map_or(path, PathBuf::from);
+        let contract_path =
+            args.contract.path.as_ref().map_or(path, |path| project.root().join(path));
         let filename = contract_path.file_name().unwrap().to_string_lossy().to_string();
         files.insert(filename, fs::read_to_string(&contract_path)?);
 
@@ -158,26 +181,27 @@ metadata output can be enabled via `extra_output = ["metadata"]` in `foundry.tom
         Ok(req)
     }
 
+    /// Returns the human readable message for the sourcify verification response, if any.
     fn process_sourcify_response(
         &self,
         response: Option<Vec<SourcifyResponseElement>>,
-    ) -> Result<()> {
-        let Some([response, ..]) = response.as_deref() else { return Ok(()) };
-        match response.status.as_str() {
+    ) -> Result<Option<String>> {
+        let Some([response, ..]) = response.as_deref() else { return Ok(None) };
+        let message = match response.status.as_str() {
             "perfect" => {
                 if let Some(ts) = &response.storage_timestamp {
-                    println!("Contract source code already verified. Storage Timestamp: {ts}");
+                    format!("Contract source code already verified. Storage Timestamp: {ts}")
                 } else {
-                    println!("Contract successfully verified");
+                    "Contract successfully verified".to_string()
                 }
             }
             "partial" => {
-                println!("The recompiled contract partially matches the deployed version");
+                "The recompiled contract partially matches the deployed version".to_string()
             }
-            "false" => println!("Contract source code is not verified"),
+            "false" => "Contract source code is not verified".to_string(),
             s => eyre::bail!("Unknown status from sourcify. Status: {s:?}"),
-        }
-        Ok(())
+        };
+        Ok(Some(message))
     }
 }
 
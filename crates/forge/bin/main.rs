@@ -9,7 +9,7 @@ use foundry_cli::{handler, utils};
 mod cmd;
 mod opts;
 
-use cmd::{cache::CacheSubcommands, generate::GenerateSubcommands, watch};
+use cmd::{cache::CacheSubcommands, generate::GenerateSubcommands, verify::VerifyExitCode, watch};
 use opts::{Opts, Subcommands};
 
 fn main() -> Result<()> {
@@ -19,6 +19,12 @@ fn main() -> Result<()> {
     utils::enable_paint();
 
     let opts = Opts::parse();
+    if !matches!(opts.sub, Subcommands::Test(_) | Subcommands::Script(_)) {
+        // test and script install their own shell
+        foundry_common::shell::set_shell(
+            foundry_common::shell::Shell::default().with_verbose_level(opts.verbosity),
+        )?;
+    }
     match opts.sub {
         Subcommands::Test(cmd) => {
             if cmd.is_watch() {
@@ -46,8 +52,10 @@ fn main() -> Result<()> {
             }
         }
         Subcommands::Debug(cmd) => utils::block_on(cmd.run()),
-        Subcommands::VerifyContract(args) => utils::block_on(args.run()),
-        Subcommands::VerifyCheck(args) => utils::block_on(args.run()),
+        Subcommands::VerifyContract(args) => exit_verify(utils::block_on(args.run()).map(|_| ())),
+        Subcommands::VerifyCheck(args) => exit_verify(utils::block_on(args.run())),
+        Subcommands::VerifyBatch(args) => utils::block_on(args.run()),
+        Subcommands::VerifyCheckBatch(args) => utils::block_on(args.run()),
         Subcommands::Cache(cmd) => match cmd.sub {
             CacheSubcommands::Clean(cmd) => cmd.run(),
             CacheSubcommands::Ls(cmd) => cmd.run(),
@@ -103,3 +111,16 @@ fn main() -> Result<()> {
         },
     }
 }
+
+/// Exits with the [VerifyExitCode] of a failed verification command.
+///
+/// Errors that aren't specific to verification are returned instead, to exit as usual.
+fn exit_verify(result: Result<()>) -> Result<()> {
+    match VerifyExitCode::of(&result) {
+        VerifyExitCode::Verified | VerifyExitCode::Failed => result,
+        code => {
+            eprintln!("Error: {:?}", result.unwrap_err());
+            std::process::exit(code as i32)
+        }
+    }
+}
@@ -17,9 +17,9 @@ use crate::cmd::{
     script::ScriptArgs,
     selectors::SelectorsSubcommands,
     snapshot, test, tree, update,
-    verify::{VerifyArgs, VerifyCheckArgs},
+    verify::{VerifyArgs, VerifyBatchArgs, VerifyCheckArgs, VerifyCheckBatchArgs},
 };
-use clap::{Parser, Subcommand, ValueHint};
+use clap::{ArgAction, Parser, Subcommand, ValueHint};
 use std::path::PathBuf;
 
 const VERSION_MESSAGE: &str = concat!(
@@ -40,6 +40,19 @@ const VERSION_MESSAGE: &str = concat!(
     next_display_order = None,
 )]
 pub struct Opts {
+    /// Verbosity level of the output.
+    ///
+    /// Pass multiple times to increase the verbosity (e.g. -v, -vv). Commands print only their
+    /// result by default.
+    ///
+    /// Verbosity levels:
+    /// - 1: Print progress messages
+    /// - 2: Print details useful for debugging, e.g. the resolved chain and compiler version
+    ///
+    /// `forge test` and `forge script` ignore it, use their own `-v` instead.
+    #[clap(long, short, verbatim_doc_comment, action = ArgAction::Count)]
+    pub verbosity: u8,
+
     #[clap(subcommand)]
     pub sub: Subcommands,
 }
@@ -97,6 +110,14 @@ pub enum Subcommands {
     #[clap(visible_alias = "vc")]
     VerifyCheck(VerifyCheckArgs),
 
+    /// Verify multiple smart contracts listed in a mapping file.
+    #[clap(visible_alias = "vb")]
+    VerifyBatch(VerifyBatchArgs),
+
+    /// Check the verification status of multiple submissions listed in a file.
+    #[clap(visible_alias = "vcb")]
+    VerifyCheckBatch(VerifyCheckBatchArgs),
+
     /// Deploy a smart contract.
     #[clap(visible_alias = "c")]
     Create(CreateArgs),
@@ -1596,6 +1596,34 @@ forgetest_init!(can_build_sizes_repeatedly, |prj, cmd| {
     assert!(unchanged.contains(table), "{}", table);
 });
 
+// checks that build --fail-size fails if a contract exceeds the size limit
+forgetest_init!(can_fail_build_on_size_limit, |prj, cmd| {
+    prj.add_source(
+        "Large",
+        &format!(
+            r#"
+contract Large {{
+    function data() external pure returns (bytes memory) {{
+        return hex"{}";
+    }}
+}}
+   "#,
+            "ff".repeat(25_000)
+        ),
+    )
+    .unwrap();
+
+    cmd.args(["build", "--sizes"]);
+    cmd.assert_err();
+
+    cmd.forge_fuse().args(["build", "--fail-size"]);
+    cmd.assert_err();
+
+    // the size limit is enforced even if no sizes are printed
+    cmd.forge_fuse().args(["build", "--fail-size", "--format-json"]);
+    cmd.assert_err();
+});
+
 // checks that build --names includes all contracts even if unchanged
 forgetest_init!(can_build_names_repeatedly, |prj, cmd| {
     prj.clear_cache();